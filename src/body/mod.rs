@@ -0,0 +1,102 @@
+//! Body wrappers that can be inserted into a Hyper pipeline.
+//! 
+//! These types implement `http_body::Body` themselves, so they can be used
+//! anywhere a Hyper body is expected while observing the data that flows
+//! through them.
+
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use bytes::Buf;
+use http_body::{Body as HttpBody, SizeHint};
+use hyper::{Body, HeaderMap};
+
+/// A body wrapper that counts the bytes passing through it.
+/// 
+/// `MeteredBody` forwards the data and trailers of the wrapped body unchanged
+/// and adds the size of every data frame to a shared counter. The counter can
+/// be cloned before the body is handed off and read once the body has been sent.
+/// 
+/// # Examples
+/// 
+/// ```rust,no_run
+/// use b_stb::body::MeteredBody;
+/// use hyper::Body;
+/// use std::sync::atomic::Ordering;
+/// 
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let body = MeteredBody::new(Body::from("Hello"));
+///     let counter = body.counter();
+///     
+///     hyper::body::to_bytes(body).await?;
+///     assert_eq!(counter.load(Ordering::Relaxed), 5);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct MeteredBody<B = Body> {
+    inner: B,
+    bytes: Arc<AtomicU64>,
+}
+
+impl<B> MeteredBody<B> {
+    /// Wraps `inner` with a new byte counter starting at zero.
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            bytes: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Returns a handle to the shared byte counter.
+    pub fn counter(&self) -> Arc<AtomicU64> {
+        self.bytes.clone()
+    }
+
+    /// Returns the number of bytes that have passed through the body so far.
+    pub fn bytes(&self) -> u64 {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    /// Consumes the wrapper and returns the inner body.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B> HttpBody for MeteredBody<B>
+where
+    B: HttpBody + Unpin,
+{
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let poll = Pin::new(&mut self.inner).poll_data(cx);
+        if let Poll::Ready(Some(Ok(data))) = &poll {
+            self.bytes.fetch_add(data.remaining() as u64, Ordering::Relaxed);
+        }
+        poll
+    }
+
+    fn poll_trailers(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        Pin::new(&mut self.inner).poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
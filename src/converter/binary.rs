@@ -0,0 +1,1140 @@
+use std::error::Error;
+use std::io::{self, Cursor};
+
+use bytes::{Bytes, BytesMut};
+use hyper::{Body, Response};
+
+use crate::error::StreamConverterError;
+use crate::util::{find_subslice, guess_extension};
+use super::StreamConverter;
+
+/// Returns true if `error` is a Hyper error caused by the connection being
+/// reset or closed before the message was complete.
+fn is_connection_reset(error: &StreamConverterError) -> bool {
+    let StreamConverterError::HyperError(e) = error else {
+        return false;
+    };
+    if e.is_incomplete_message() {
+        return true;
+    }
+    let mut source = e.source();
+    while let Some(err) = source {
+        if let Some(io_error) = err.downcast_ref::<io::Error>() {
+            return matches!(
+                io_error.kind(),
+                io::ErrorKind::ConnectionReset | io::ErrorKind::ConnectionAborted
+            );
+        }
+        source = err.source();
+    }
+    false
+}
+
+/// Describes how a read of a body ended.
+#[derive(Debug)]
+pub enum ReadOutcome {
+    /// The body was read to the end
+    Complete,
+    /// Reading stopped because the body exceeded a size limit
+    Truncated {
+        /// The size limit that was exceeded
+        limit: usize,
+    },
+    /// Reading stopped because of an error
+    Failed(StreamConverterError),
+}
+
+/// A compression format recognised by its leading magic bytes.
+///
+/// Brotli streams have no magic number and therefore cannot be detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// gzip (`1F 8B`)
+    Gzip,
+    /// Zstandard (`28 B5 2F FD`)
+    Zstd,
+    /// bzip2 (`BZh`)
+    Bzip2,
+    /// xz (`FD 37 7A 58 5A 00`)
+    Xz,
+    /// LZ4 frame format (`04 22 4D 18`)
+    Lz4,
+}
+
+/// Magic bytes of each detectable compression format.
+const COMPRESSION_MAGIC: &[(&[u8], Compression)] = &[
+    (b"\x1f\x8b", Compression::Gzip),
+    (b"\x28\xb5\x2f\xfd", Compression::Zstd),
+    (b"BZh", Compression::Bzip2),
+    (b"\xfd7zXZ\x00", Compression::Xz),
+    (b"\x04\x22\x4d\x18", Compression::Lz4),
+];
+
+impl Compression {
+    /// Detects the compression format from the leading bytes of `bytes`.
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        COMPRESSION_MAGIC
+            .iter()
+            .find(|(magic, _)| bytes.starts_with(magic))
+            .map(|&(_, format)| format)
+    }
+}
+
+/// Counts of each class of byte in a body, as tallied by
+/// `body_to_bytes_with_classes`.
+///
+/// Every byte falls into exactly one class, so the counts sum to the body's length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ByteClasses {
+    /// Visible ASCII characters (`0x21..=0x7E`)
+    pub printable: usize,
+    /// ASCII whitespace: space, tab, line feed, vertical tab, form feed and carriage return
+    pub whitespace: usize,
+    /// Other ASCII control bytes (`0x00..=0x1F` and `0x7F`)
+    pub control: usize,
+    /// Bytes with the high bit set (`0x80..=0xFF`)
+    pub high_bit: usize,
+}
+
+impl ByteClasses {
+    /// Adds the bytes of `chunk` to the counts.
+    fn tally(&mut self, chunk: &[u8]) {
+        for &b in chunk {
+            match b {
+                b' ' | b'\t' | b'\n' | 0x0B | 0x0C | b'\r' => self.whitespace += 1,
+                0x21..=0x7E => self.printable += 1,
+                0x80..=0xFF => self.high_bit += 1,
+                _ => self.control += 1,
+            }
+        }
+    }
+
+    /// Returns the total number of bytes counted.
+    pub fn total(&self) -> usize {
+        self.printable + self.whitespace + self.control + self.high_bit
+    }
+}
+
+impl StreamConverter {
+    /// Converts a Hyper body into a payload by splitting off and verifying a trailing footer.
+    ///
+    /// This suits self-describing formats that append a checksum (such as a CRC32)
+    /// or length footer to the payload. The last `footer_len` bytes of the body are
+    /// passed to `verify` along with the payload that precedes them.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    /// * `footer_len` - The length of the footer in bytes
+    /// * `verify` - Called with `(payload, footer)`; returns whether the footer is valid
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the payload without its footer, a `ChecksumMismatch` error
+    /// if verification fails, or a `FramingError` if the body is shorter than the footer
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::{Body, Response};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     // A one-byte footer holding the payload length
+    ///     let body = Body::from(vec![b'a', b'b', b'c', 3]);
+    ///
+    ///     let payload = converter
+    ///         .body_to_bytes_strip_footer(body, 1, |payload, footer| payload.len() == footer[0] as usize)
+    ///         .await?;
+    ///     assert_eq!(payload, b"abc");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_strip_footer<F>(
+        &self,
+        body: Body,
+        footer_len: usize,
+        verify: F,
+    ) -> Result<Vec<u8>, StreamConverterError>
+    where
+        F: Fn(&[u8], &[u8]) -> bool,
+    {
+        let mut bytes = self.body_to_bytes(body).await?;
+        if bytes.len() < footer_len {
+            return Err(StreamConverterError::FramingError(format!(
+                "body is {} bytes, shorter than the {}-byte footer",
+                bytes.len(),
+                footer_len
+            )));
+        }
+
+        let footer = bytes.split_off(bytes.len() - footer_len);
+        if !verify(&bytes, &footer) {
+            return Err(StreamConverterError::ChecksumMismatch);
+        }
+        Ok(bytes)
+    }
+
+    /// Converts a Hyper body into a payload by skipping a fixed-size binary header.
+    ///
+    /// Many binary formats start with a magic number or fixed header. The first
+    /// `header_len` bytes are consumed and, if `verify_magic` is given, checked to
+    /// begin with those bytes as soon as the header has arrived. The rest of the
+    /// body is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    /// * `header_len` - The length of the header in bytes
+    /// * `verify_magic` - Optional bytes the header must start with
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the payload after the header, or a `FramingError` if
+    /// the magic does not match or the body is shorter than the header
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from(&b"\x89PNG\r\n\x1a\npayload"[..]);
+    ///
+    ///     let payload = converter
+    ///         .body_to_bytes_skip_header(body, 8, Some(b"\x89PNG"))
+    ///         .await?;
+    ///     assert_eq!(payload, b"payload");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_skip_header(
+        &self,
+        body: Body,
+        header_len: usize,
+        verify_magic: Option<&[u8]>,
+    ) -> Result<Vec<u8>, StreamConverterError> {
+        let mut chunks = self.chunks(body);
+        let mut header = Vec::with_capacity(header_len);
+        let mut payload = Vec::new();
+        if header_len == 0 {
+            check_magic(&header, verify_magic)?;
+        }
+
+        while let Some(chunk) = chunks.next_chunk().await {
+            let mut chunk = chunk?;
+            if header.len() < header_len {
+                let take = (header_len - header.len()).min(chunk.len());
+                header.extend_from_slice(&chunk.split_to(take));
+                if header.len() == header_len {
+                    check_magic(&header, verify_magic)?;
+                }
+            }
+            payload.extend_from_slice(&chunk);
+        }
+
+        if header.len() < header_len {
+            return Err(StreamConverterError::FramingError(format!(
+                "body is {} bytes, shorter than the {}-byte header",
+                header.len(),
+                header_len
+            )));
+        }
+        Ok(payload)
+    }
+
+    /// Converts a Hyper body into a vector of bytes along with the size of every chunk received.
+    ///
+    /// The chunk lengths are recorded in arrival order, so an identically framed
+    /// chunked body can be reconstructed for deterministic replay in tests.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the bytes and the ordered chunk lengths, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let (bytes, frames) = converter.body_to_bytes_with_frames(Body::from("Hello")).await?;
+    ///     assert_eq!(bytes, b"Hello");
+    ///     assert_eq!(frames, vec![5]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_with_frames(
+        &self,
+        body: Body,
+    ) -> Result<(Vec<u8>, Vec<usize>), StreamConverterError> {
+        let mut chunks = self.chunks(body);
+        let mut bytes = Vec::new();
+        let mut frames = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = chunk?;
+            frames.push(chunk.len());
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok((bytes, frames))
+    }
+
+    /// Converts a Hyper body into a vector of bytes along with a file extension
+    /// guessed from its leading bytes.
+    ///
+    /// This makes it easy to save downloads under a sensible name when the server
+    /// provides none. See [`guess_extension`](crate::util::guess_extension) for the
+    /// recognized formats.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the bytes and the guessed extension (without a leading
+    /// dot), or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from(&b"%PDF-1.7 ..."[..]);
+    ///
+    ///     let (bytes, extension) = converter.body_to_bytes_with_extension(body).await?;
+    ///     let name = format!("download.{}", extension.unwrap_or("bin"));
+    ///     tokio::fs::write(name, bytes).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_with_extension(
+        &self,
+        body: Body,
+    ) -> Result<(Vec<u8>, Option<&'static str>), StreamConverterError> {
+        let bytes = self.body_to_bytes(body).await?;
+        let extension = guess_extension(&bytes);
+        Ok((bytes, extension))
+    }
+
+    /// Converts a Hyper body into the parts before and after the first occurrence of a delimiter.
+    ///
+    /// The split happens while streaming: bytes are appended to the first half
+    /// until the delimiter arrives, and to the second half afterwards, so the body
+    /// is never collected and then copied. The delimiter itself is not included
+    /// in either half. This suits `key\0value` and similar two-part payloads.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to split
+    /// * `delim` - The delimiter byte
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the bytes before and after the delimiter, or a
+    /// `FramingError` if the delimiter never appears
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let (key, value) = converter.body_split_once(Body::from("key\0value"), 0).await?;
+    ///     assert_eq!(key, b"key");
+    ///     assert_eq!(value, b"value");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_split_once(
+        &self,
+        body: Body,
+        delim: u8,
+    ) -> Result<(Vec<u8>, Vec<u8>), StreamConverterError> {
+        let mut chunks = self.chunks(body);
+        let mut before = Vec::new();
+        let mut after = Vec::new();
+        let mut found = false;
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = chunk?;
+            if found {
+                after.extend_from_slice(&chunk);
+                continue;
+            }
+            match chunk.iter().position(|&b| b == delim) {
+                Some(pos) => {
+                    before.extend_from_slice(&chunk[..pos]);
+                    after.extend_from_slice(&chunk[pos + 1..]);
+                    found = true;
+                }
+                None => before.extend_from_slice(&chunk),
+            }
+        }
+
+        if !found {
+            return Err(StreamConverterError::FramingError(format!(
+                "delimiter 0x{:02x} not found in {}-byte body",
+                delim,
+                before.len()
+            )));
+        }
+        Ok((before, after))
+    }
+
+    /// Converts a Hyper body into a `std::io::Cursor` for random access.
+    ///
+    /// The body is collected in full and wrapped in a cursor positioned at the
+    /// start, so synchronous parsers that need `Read + Seek` can operate on it.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a cursor over the body bytes, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    /// use std::io::{Read, Seek, SeekFrom};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let mut cursor = converter.body_to_cursor(Body::from("header:payload")).await?;
+    ///
+    ///     cursor.seek(SeekFrom::Start(7))?;
+    ///     let mut payload = String::new();
+    ///     cursor.read_to_string(&mut payload)?;
+    ///     assert_eq!(payload, "payload");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_cursor(&self, body: Body) -> Result<Cursor<Vec<u8>>, StreamConverterError> {
+        Ok(Cursor::new(self.body_to_bytes(body).await?))
+    }
+
+    /// Searches a Hyper body for the first occurrence of `needle`.
+    ///
+    /// The body is streamed with a sliding window of `needle.len() - 1` bytes
+    /// carried between chunks, so matches that span chunk boundaries are found
+    /// without buffering the whole body. Reading stops as soon as a match is found.
+    /// An empty needle matches at offset 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to search
+    /// * `needle` - The byte sequence to look for
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the byte offset of the first match, or `None` if there
+    /// is no match, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let offset = converter.body_find(Body::from("header--MARK--payload"), b"MARK").await?;
+    ///     assert_eq!(offset, Some(8));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_find(&self, body: Body, needle: &[u8]) -> Result<Option<usize>, StreamConverterError> {
+        if needle.is_empty() {
+            return Ok(Some(0));
+        }
+        let mut chunks = self.chunks(body);
+        let mut window = Vec::new();
+        // Offset in the body of the first byte in `window`
+        let mut window_start = 0;
+        while let Some(chunk) = chunks.next_chunk().await {
+            window.extend_from_slice(&chunk?);
+            if let Some(pos) = find_subslice(&window, needle) {
+                return Ok(Some(window_start + pos));
+            }
+            let keep = window.len().min(needle.len() - 1);
+            let drop = window.len() - keep;
+            window.drain(..drop);
+            window_start += drop;
+        }
+        Ok(None)
+    }
+
+    /// Converts a Hyper body into bytes, aborting if any forbidden token appears.
+    ///
+    /// Each chunk is checked as it arrives, together with enough of the preceding
+    /// data to catch tokens that span chunk boundaries, so a blocked response is
+    /// rejected as soon as the token is seen rather than after the whole body has
+    /// been read. Empty tokens are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    /// * `forbidden` - The byte sequences that must not appear in the body
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the bytes, or a `PolicyViolation` error naming the
+    /// first forbidden token found
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from("<p>hello</p>");
+    ///
+    ///     let bytes = converter.body_to_bytes_filtered(body, &[b"<script", b"javascript:"]).await?;
+    ///     assert_eq!(bytes, b"<p>hello</p>");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_filtered(
+        &self,
+        body: Body,
+        forbidden: &[&[u8]],
+    ) -> Result<Vec<u8>, StreamConverterError> {
+        let longest = forbidden.iter().map(|token| token.len()).max().unwrap_or(0);
+        let mut chunks = self.chunks(body);
+        let mut bytes = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().await {
+            // Re-check the tail of the previous data for tokens spanning the boundary
+            let start = bytes.len().saturating_sub(longest.saturating_sub(1));
+            bytes.extend_from_slice(&chunk?);
+            let window = &bytes[start..];
+            let violation = forbidden
+                .iter()
+                .filter(|token| !token.is_empty())
+                .filter_map(|token| find_subslice(window, token).map(|pos| (pos, token)))
+                .min_by_key(|&(pos, _)| pos);
+            if let Some((_, token)) = violation {
+                return Err(StreamConverterError::PolicyViolation { token: token.to_vec() });
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Converts a Hyper body into bytes, replacing every occurrence of `from` with `to`
+    /// as chunks arrive.
+    ///
+    /// Matches are found left to right without overlapping, exactly as with a
+    /// replace over the collected data, including matches that span chunk
+    /// boundaries. Replacement output is never searched again, so `to` may
+    /// contain `from`. When `from` and `to` differ in length, the result is
+    /// longer or shorter than the body and later offsets shift accordingly; any
+    /// size limit applies to the body as received, not to the result. An empty
+    /// `from` leaves the body unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    /// * `from` - The byte sequence to search for
+    /// * `to` - The byte sequence to substitute for each match
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the rewritten bytes, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from("token=secret123&user=ann");
+    ///
+    ///     let bytes = converter.body_to_bytes_replace(body, b"secret123", b"[redacted]").await?;
+    ///     assert_eq!(bytes, b"token=[redacted]&user=ann");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_replace(
+        &self,
+        body: Body,
+        from: &[u8],
+        to: &[u8],
+    ) -> Result<Vec<u8>, StreamConverterError> {
+        if from.is_empty() {
+            return self.body_to_bytes(body).await;
+        }
+        let mut chunks = self.chunks(body);
+        let mut output = Vec::new();
+        // Input not yet emitted, which may hold the start of a match
+        let mut pending = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().await {
+            pending.extend_from_slice(&chunk?);
+            let mut pos = 0;
+            while let Some(found) = find_subslice(&pending[pos..], from) {
+                output.extend_from_slice(&pending[pos..pos + found]);
+                output.extend_from_slice(to);
+                pos += found + from.len();
+            }
+            // Hold back a tail that could be the start of a match in the next chunk
+            let keep = (pending.len() - pos).min(from.len() - 1);
+            let emit_end = pending.len() - keep;
+            output.extend_from_slice(&pending[pos..emit_end]);
+            pending.drain(..emit_end);
+        }
+        output.extend_from_slice(&pending);
+        Ok(output)
+    }
+
+    /// Converts a Hyper body into a vector of bytes and reports the vector's final capacity.
+    ///
+    /// Comparing the capacity against the length shows how much memory was
+    /// over-allocated by `Vec` growth, which is useful when tuning allocation.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the bytes and their `Vec::capacity()`, or a StreamConverterError
+    pub async fn body_to_bytes_with_capacity_report(
+        &self,
+        body: Body,
+    ) -> Result<(Vec<u8>, usize), StreamConverterError> {
+        let bytes = self.body_to_bytes(body).await?;
+        let capacity = bytes.capacity();
+        Ok((bytes, capacity))
+    }
+
+    /// Converts a Hyper response body into bytes, enforcing a size limit read from a response header.
+    ///
+    /// The limit is parsed from the header named `header` (for example a custom
+    /// `X-Max-Body` header). If the header is absent or is not a valid number,
+    /// `default` is used instead. The limit is checked as chunks arrive, before
+    /// they are appended.
+    ///
+    /// # Arguments
+    ///
+    /// * `resp` - The Hyper response whose body to convert
+    /// * `header` - The name of the header holding the limit in bytes
+    /// * `default` - The limit to use when the header is missing or invalid
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the bytes, or a `SizeLimitExceeded` error if the body
+    /// is larger than the limit
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::{Body, Response};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let resp = Response::builder()
+    ///         .header("X-Max-Body", "1024")
+    ///         .body(Body::from("small"))?;
+    ///
+    ///     let bytes = converter.body_to_bytes_header_limited(resp, "X-Max-Body", 64).await?;
+    ///     assert_eq!(bytes, b"small");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_header_limited(
+        &self,
+        resp: Response<Body>,
+        header: &str,
+        default: usize,
+    ) -> Result<Vec<u8>, StreamConverterError> {
+        let limit = resp
+            .headers()
+            .get(header)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .unwrap_or(default);
+
+        let mut chunks = self.chunks(resp.into_body());
+        let mut bytes = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = chunk?;
+            let read_so_far = bytes.len() + chunk.len();
+            if read_so_far > limit {
+                return Err(StreamConverterError::SizeLimitExceeded { limit, read_so_far });
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(bytes)
+    }
+
+    /// Reads a Hyper body and reports exactly how the read ended.
+    ///
+    /// Unlike `body_to_bytes`, this never discards data: whatever bytes were
+    /// collected are always returned, together with a [`ReadOutcome`] describing
+    /// whether the body completed, was truncated by a size limit, or failed. A
+    /// truncated read returns exactly the first `limit` bytes of the body, even
+    /// when the limit falls in the middle of a chunk.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to read
+    ///
+    /// # Returns
+    ///
+    /// The bytes collected and the outcome of the read
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use b_stb::converter::ReadOutcome;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let converter = StreamConverter::new();
+    ///     let (bytes, outcome) = converter.body_to_bytes_outcome(Body::from("data")).await;
+    ///
+    ///     match outcome {
+    ///         ReadOutcome::Complete => println!("read {} bytes", bytes.len()),
+    ///         ReadOutcome::Truncated { limit } => println!("stopped at the {} byte limit", limit),
+    ///         ReadOutcome::Failed(e) => println!("failed after {} bytes: {}", bytes.len(), e),
+    ///     }
+    /// }
+    /// ```
+    pub async fn body_to_bytes_outcome(&self, body: Body) -> (Vec<u8>, ReadOutcome) {
+        let mut chunks = self.chunks(body);
+        let mut bytes = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().await {
+            match chunk {
+                Ok(chunk) => bytes.extend_from_slice(&chunk),
+                Err(StreamConverterError::SizeLimitExceeded { limit, .. }) => {
+                    // Keep the part of the crossing chunk that still fits
+                    if let Some(chunk) = chunks.take_overflow() {
+                        let room = limit.saturating_sub(bytes.len()).min(chunk.len());
+                        bytes.extend_from_slice(&chunk[..room]);
+                    }
+                    return (bytes, ReadOutcome::Truncated { limit });
+                }
+                Err(e) => return (bytes, ReadOutcome::Failed(e)),
+            }
+        }
+        (bytes, ReadOutcome::Complete)
+    }
+
+    /// Reads a Hyper body and returns whatever was collected along with the error
+    /// that stopped it, if any.
+    ///
+    /// If the body fails partway through, such as on a connection reset, the bytes
+    /// received before the failure are kept rather than discarded, which allows
+    /// salvaging partial downloads or logging the start of a truncated error page.
+    /// Unlike `body_to_bytes_outcome`, the error is returned exactly as
+    /// `body_to_bytes` would report it.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to read
+    ///
+    /// # Returns
+    ///
+    /// The bytes collected and `Some(error)` if reading stopped early, or `None`
+    /// if the body was read to the end
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let converter = StreamConverter::new();
+    ///     let (bytes, error) = converter.body_to_bytes_partial(Body::from("data")).await;
+    ///
+    ///     if let Some(e) = error {
+    ///         let head = &bytes[..bytes.len().min(4096)];
+    ///         eprintln!("failed: {}; received so far: {}", e, String::from_utf8_lossy(head));
+    ///     }
+    /// }
+    /// ```
+    pub async fn body_to_bytes_partial(&self, body: Body) -> (Vec<u8>, Option<StreamConverterError>) {
+        let mut chunks = self.chunks(body);
+        let mut bytes = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().await {
+            match chunk {
+                Ok(chunk) => bytes.extend_from_slice(&chunk),
+                Err(e) => return (bytes, Some(e)),
+            }
+        }
+        (bytes, None)
+    }
+
+    /// Converts a Hyper body into bytes, reporting whether the stream ended cleanly.
+    ///
+    /// If the connection is reset partway through the body, the bytes received
+    /// before the reset are returned instead of an error, flagged as not clean.
+    /// Resets are recognized through `hyper::Error::is_incomplete_message` or an
+    /// underlying `ConnectionReset` or `ConnectionAborted` I/O error. Any other
+    /// failure is returned as an error, as `body_to_bytes` would report it.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the bytes and `true` if the body ended normally or
+    /// `false` if the connection was reset, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let (bytes, clean) = converter.body_to_bytes_clean(Body::from("data")).await?;
+    ///
+    ///     if !clean {
+    ///         eprintln!("connection reset after {} bytes", bytes.len());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_clean(&self, body: Body) -> Result<(Vec<u8>, bool), StreamConverterError> {
+        match self.body_to_bytes_partial(body).await {
+            (bytes, None) => Ok((bytes, true)),
+            (bytes, Some(e)) if is_connection_reset(&e) => Ok((bytes, false)),
+            (_, Some(e)) => Err(e),
+        }
+    }
+
+    /// Converts a Hyper body into bytes and reports the offset of every NUL byte.
+    ///
+    /// NUL bytes are located as each chunk arrives, which helps pinpoint where
+    /// binary data was spliced into a supposedly textual payload. The data itself
+    /// is returned unchanged, regardless of the converter's `NulPolicy`.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the bytes and the ascending offsets of their NUL
+    /// bytes, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from(&b"text\0more\0"[..]);
+    ///
+    ///     let (bytes, positions) = converter.body_to_bytes_with_nul_positions(body).await?;
+    ///     assert_eq!(bytes.len(), 10);
+    ///     assert_eq!(positions, vec![4, 9]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_with_nul_positions(
+        &self,
+        body: Body,
+    ) -> Result<(Vec<u8>, Vec<usize>), StreamConverterError> {
+        let mut chunks = self.chunks(body);
+        let mut bytes = Vec::new();
+        let mut positions = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = chunk?;
+            let offset = bytes.len();
+            positions.extend(
+                chunk
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &b)| b == 0)
+                    .map(|(i, _)| offset + i),
+            );
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok((bytes, positions))
+    }
+
+    /// Converts a Hyper body into bytes along with the offset of every newline.
+    ///
+    /// The index is built during the single collection pass, so no second scan
+    /// of the data is needed. Line `n` (counting from 0) starts at offset 0 for
+    /// the first line and at `index[n - 1] + 1` otherwise, which allows jumping
+    /// to any line in constant time.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the bytes and the ascending offsets of every `\n`,
+    /// or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let (bytes, index) = converter
+    ///         .body_to_bytes_with_newline_index(Body::from("ab\ncd\nef"))
+    ///         .await?;
+    ///
+    ///     // Jump straight to the third line
+    ///     let start = index[1] as usize + 1;
+    ///     assert_eq!(&bytes[start..], b"ef");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_with_newline_index(
+        &self,
+        body: Body,
+    ) -> Result<(Vec<u8>, Vec<u64>), StreamConverterError> {
+        let mut chunks = self.chunks(body);
+        let mut bytes = Vec::new();
+        let mut index = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = chunk?;
+            let offset = bytes.len() as u64;
+            index.extend(
+                chunk
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &b)| b == b'\n')
+                    .map(|(i, _)| offset + i as u64),
+            );
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok((bytes, index))
+    }
+
+    /// Converts a Hyper body into bytes and counts each class of byte it contains.
+    ///
+    /// The counts are tallied during collection, so ratios for a quick
+    /// text/binary heuristic are available without a second pass over the data.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the bytes and their [`ByteClasses`], or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let (bytes, classes) = converter.body_to_bytes_with_classes(Body::from("plain text")).await?;
+    ///
+    ///     let binary_ratio = (classes.control + classes.high_bit) as f64 / classes.total().max(1) as f64;
+    ///     println!("{} bytes, {:.0}% binary", bytes.len(), binary_ratio * 100.0);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_with_classes(
+        &self,
+        body: Body,
+    ) -> Result<(Vec<u8>, ByteClasses), StreamConverterError> {
+        let mut chunks = self.chunks(body);
+        let mut bytes = Vec::new();
+        let mut classes = ByteClasses::default();
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = chunk?;
+            classes.tally(&chunk);
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok((bytes, classes))
+    }
+
+    /// Converts a Hyper body into bytes and detects its compression format without decompressing it.
+    ///
+    /// The format is recognised from the leading magic bytes, so compressed
+    /// payloads can be stored as-is and decompressed lazily later. The bytes are
+    /// returned exactly as received.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the raw bytes and the detected [`Compression`], or
+    /// `None` if no known format was recognised, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use b_stb::converter::Compression;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from(vec![0x1f, 0x8b, 0x08, 0x00]);
+    ///
+    ///     let (bytes, format) = converter.body_to_bytes_detect_compression(body).await?;
+    ///     assert_eq!(format, Some(Compression::Gzip));
+    ///     assert_eq!(bytes.len(), 4);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_detect_compression(
+        &self,
+        body: Body,
+    ) -> Result<(Vec<u8>, Option<Compression>), StreamConverterError> {
+        let bytes = self.body_to_bytes(body).await?;
+        let format = Compression::detect(&bytes);
+        Ok((bytes, format))
+    }
+
+    /// Reads a Hyper body that must consist of exactly one chunk and returns it without copying.
+    ///
+    /// This asserts the invariant of protocols that guarantee single-frame
+    /// responses. Reading stops as soon as a second chunk arrives.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to read
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the single chunk, or a `NotSingleChunk` error if the
+    /// body is empty or has more than one chunk
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let chunk = converter.body_to_single_chunk(Body::from("frame")).await?;
+    ///     assert_eq!(&chunk[..], b"frame");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_single_chunk(&self, body: Body) -> Result<Bytes, StreamConverterError> {
+        let mut chunks = self.chunks(body);
+        let chunk = match chunks.next_chunk().await {
+            Some(chunk) => chunk?,
+            None => return Err(StreamConverterError::NotSingleChunk { chunks: 0 }),
+        };
+        match chunks.next_chunk().await {
+            None => Ok(chunk),
+            Some(Err(e)) => Err(e),
+            Some(Ok(_)) => Err(StreamConverterError::NotSingleChunk { chunks: 2 }),
+        }
+    }
+
+    /// Converts a Hyper body into `Bytes`, avoiding a copy when the body is a single chunk.
+    ///
+    /// When the body yields exactly one chunk, that chunk's reference-counted
+    /// `Bytes` is returned directly. Otherwise the chunks are concatenated into a
+    /// `BytesMut`. The result can be cloned cheaply.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the body as `Bytes`, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let bytes = converter.body_to_bytes_zerocopy(Body::from("Hello")).await?;
+    ///     let shared = bytes.clone();
+    ///     assert_eq!(&shared[..], b"Hello");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_zerocopy(&self, body: Body) -> Result<Bytes, StreamConverterError> {
+        let mut chunks = self.chunks(body);
+        let first = match chunks.next_chunk().await {
+            Some(chunk) => chunk?,
+            None => return Ok(Bytes::new()),
+        };
+        let second = match chunks.next_chunk().await {
+            Some(chunk) => chunk?,
+            None => return Ok(first),
+        };
+
+        let mut bytes = BytesMut::with_capacity(first.len() + second.len());
+        bytes.extend_from_slice(&first);
+        bytes.extend_from_slice(&second);
+        while let Some(chunk) = chunks.next_chunk().await {
+            bytes.extend_from_slice(&chunk?);
+        }
+        Ok(bytes.freeze())
+    }
+}
+
+/// Checks that a complete header begins with the expected magic bytes, if any.
+fn check_magic(header: &[u8], magic: Option<&[u8]>) -> Result<(), StreamConverterError> {
+    match magic {
+        Some(magic) if !header.starts_with(magic) => Err(StreamConverterError::FramingError(
+            format!("header does not start with the expected {}-byte magic", magic.len()),
+        )),
+        _ => Ok(()),
+    }
+}
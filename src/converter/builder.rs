@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use super::convert::DEFAULT_FORWARD_BUFFER;
+use super::reader::LengthCheck;
+use super::text::{NulPolicy, TrailingNewline};
+use super::StreamConverter;
+
+/// Default size of the internal read buffer (8KB).
+const DEFAULT_BUFFER_SIZE: usize = 8192;
+
+/// Builds a `StreamConverter` with several options set at once.
+///
+/// Every option starts at the same default as `StreamConverter::new()`.
+/// Options not covered by the builder can still be set on the built converter
+/// with its `with_*` methods.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use b_stb::StreamConverterBuilder;
+///
+/// let converter = StreamConverterBuilder::new()
+///     .buffer_size(16384)
+///     .max_size(10 * 1024 * 1024)
+///     .timeout(Duration::from_secs(30))
+///     .strip_bom(true)
+///     .lossy(true)
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamConverterBuilder {
+    buffer_size: usize,
+    max_size: Option<usize>,
+    timeout: Option<Duration>,
+    strip_bom: bool,
+    lossy: bool,
+}
+
+impl StreamConverterBuilder {
+    /// Creates a builder with the default options.
+    pub fn new() -> Self {
+        Self {
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            max_size: None,
+            timeout: None,
+            strip_bom: false,
+            lossy: false,
+        }
+    }
+
+    /// Sets the size of the internal buffer in bytes.
+    ///
+    /// A size of 0 would make every read return nothing, so it is treated as 1.
+    pub fn buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size.max(1);
+        self
+    }
+
+    /// Sets a hard limit on the number of bytes a conversion may read.
+    ///
+    /// See `StreamConverter::with_max_size`.
+    pub fn max_size(mut self, limit: usize) -> Self {
+        self.max_size = Some(limit);
+        self
+    }
+
+    /// Sets an overall deadline for receiving a whole body.
+    ///
+    /// See `StreamConverter::with_timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables or disables stripping a leading UTF-8 byte order mark.
+    ///
+    /// See `StreamConverter::with_strip_bom`.
+    pub fn strip_bom(mut self, enabled: bool) -> Self {
+        self.strip_bom = enabled;
+        self
+    }
+
+    /// Enables or disables lossy UTF-8 decoding in the String conversions.
+    ///
+    /// See `StreamConverter::with_lossy`.
+    pub fn lossy(mut self, enabled: bool) -> Self {
+        self.lossy = enabled;
+        self
+    }
+
+    /// Builds the configured `StreamConverter`.
+    pub fn build(self) -> StreamConverter {
+        StreamConverter {
+            buffer_size: self.buffer_size,
+            max_size: self.max_size,
+            soft_limit: None,
+            error_preview: false,
+            trailing_newline: TrailingNewline::default(),
+            nul_policy: NulPolicy::default(),
+            length_check: LengthCheck::default(),
+            lossy: self.lossy,
+            on_error: None,
+            min_rate: None,
+            yield_every: None,
+            forward_buffer: DEFAULT_FORWARD_BUFFER,
+            timeout: self.timeout,
+            chunk_timeout: None,
+            strip_bom: self.strip_bom,
+            adaptive_buffer: false,
+            concurrency_limit: None,
+        }
+    }
+}
+
+impl Default for StreamConverterBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
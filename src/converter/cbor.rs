@@ -0,0 +1,45 @@
+use hyper::Body;
+use serde::de::DeserializeOwned;
+
+use crate::error::StreamConverterError;
+use super::StreamConverter;
+
+impl StreamConverter {
+    /// Converts a Hyper body into a value deserialized from CBOR.
+    ///
+    /// The body is collected in full before being decoded.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing either the deserialized value or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from(vec![0x83, 0x01, 0x02, 0x03]);
+    ///
+    ///     let values: Vec<u32> = converter.body_to_cbor(body).await?;
+    ///     assert_eq!(values, vec![1, 2, 3]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_cbor<T>(&self, body: Body) -> Result<T, StreamConverterError>
+    where
+        T: DeserializeOwned,
+    {
+        let bytes = self.body_to_bytes(body).await?;
+        ciborium::de::from_reader(bytes.as_slice()).map_err(|e| {
+            StreamConverterError::CborError(e).with_preview(self.preview_of(&bytes))
+        })
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use futures_util::future::try_join_all;
+use futures_util::stream::{self, StreamExt};
+use hyper::Body;
+
+use crate::error::StreamConverterError;
+use super::StreamConverter;
+
+impl StreamConverter {
+    /// Collects several named Hyper bodies concurrently.
+    ///
+    /// All bodies are read at the same time. If any body fails, the whole
+    /// operation fails with a `NamedBodyError` identifying the body by name.
+    /// If the same name appears more than once, the last body wins.
+    ///
+    /// # Arguments
+    ///
+    /// * `bodies` - The bodies to collect, each paired with a name
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a map from name to body bytes, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let bodies = vec![
+    ///         ("users".to_string(), Body::from("[]")),
+    ///         ("orders".to_string(), Body::from("{}")),
+    ///     ];
+    ///
+    ///     let results = converter.collect_all(bodies).await?;
+    ///     assert_eq!(results["users"], b"[]");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn collect_all(
+        &self,
+        bodies: Vec<(String, Body)>,
+    ) -> Result<HashMap<String, Vec<u8>>, StreamConverterError> {
+        let reads = bodies.into_iter().map(|(name, body)| async move {
+            match self.body_to_bytes(body).await {
+                Ok(bytes) => Ok((name, bytes)),
+                Err(error) => Err(StreamConverterError::NamedBodyError {
+                    name,
+                    error: Box::new(error),
+                }),
+            }
+        });
+        Ok(try_join_all(reads).await?.into_iter().collect())
+    }
+
+    /// Converts several Hyper bodies into Strings, reading at most `concurrency`
+    /// of them at a time.
+    ///
+    /// Each body is converted with `body_to_string`, so the converter's size
+    /// limit, timeout and buffer settings apply per body. A failing body does not
+    /// affect the others, and results are returned in the same order as `bodies`.
+    /// A `concurrency` of 0 is treated as 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `bodies` - The bodies to convert
+    /// * `concurrency` - The maximum number of bodies to read at the same time
+    ///
+    /// # Returns
+    ///
+    /// One Result per body, in input order, containing either the String or a
+    /// StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let converter = StreamConverter::new();
+    ///     let bodies = (0..50).map(|i| Body::from(format!("page {}", i))).collect();
+    ///
+    ///     let results = converter.body_to_strings_concurrent(bodies, 8).await;
+    ///     assert_eq!(results[3].as_deref().unwrap(), "page 3");
+    /// }
+    /// ```
+    pub async fn body_to_strings_concurrent(
+        &self,
+        bodies: Vec<Body>,
+        concurrency: usize,
+    ) -> Vec<Result<String, StreamConverterError>> {
+        stream::iter(bodies)
+            .map(|body| self.body_to_string(body))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+}
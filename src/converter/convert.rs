@@ -1,8 +1,24 @@
-use tokio::io::{AsyncRead, AsyncReadExt};
-use futures_util::StreamExt;
+use std::error::Error;
+use std::fmt;
+use std::string::FromUtf8Error;
+use std::sync::Arc;
+use std::future::Future;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use bytes::{Bytes, BytesMut};
+use futures_core::Stream;
+use tokio::fs::OpenOptions;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::{mpsc, Semaphore};
+use hyper::body::HttpBody;
 use hyper::Body;
 
 use crate::error::StreamConverterError;
+use super::builder::StreamConverterBuilder;
+use super::reader::{ChunkReader, GuardedBody, LengthCheck, ReadTracker, ScratchBuffer, StreamBody};
+use super::text::{NulPolicy, TrailingNewline};
 
 /// A utility for converting various types of streams into strings or bytes.
 /// 
@@ -10,6 +26,9 @@ use crate::error::StreamConverterError;
 /// and async readers into strings or byte vectors. It handles chunked data and
 /// supports configurable buffer sizes for optimal performance.
 /// 
+/// Cloning is cheap: callbacks are shared rather than copied, so one configured
+/// converter can be cloned into each spawned task.
+/// 
 /// # Examples
 /// 
 /// ```rust,no_run
@@ -26,56 +45,902 @@ use crate::error::StreamConverterError;
 ///     Ok(())
 /// }
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct StreamConverter {
-    buffer_size: usize,
+    pub(super) buffer_size: usize,
+    pub(super) max_size: Option<usize>,
+    pub(super) soft_limit: Option<SoftLimit>,
+    pub(super) error_preview: bool,
+    pub(super) trailing_newline: TrailingNewline,
+    pub(super) nul_policy: NulPolicy,
+    pub(super) length_check: LengthCheck,
+    pub(super) lossy: bool,
+    pub(super) on_error: Option<ErrorHook>,
+    pub(super) min_rate: Option<MinRate>,
+    pub(super) yield_every: Option<usize>,
+    pub(super) forward_buffer: usize,
+    pub(super) timeout: Option<Duration>,
+    pub(super) chunk_timeout: Option<ChunkTimeout>,
+    pub(super) strip_bom: bool,
+    pub(super) adaptive_buffer: bool,
+    pub(super) concurrency_limit: Option<Arc<Semaphore>>,
 }
 
-impl StreamConverter {
-    /// Creates a new StreamConverter with the default buffer size (8KB).
+/// Default number of chunks that forwarding methods may read ahead of their consumer.
+pub(super) const DEFAULT_FORWARD_BUFFER: usize = 1;
+
+/// Largest allocation made up front from a body's own size hint, so a bogus
+/// `Content-Length` cannot force a giant allocation.
+const MAX_PREALLOCATION: usize = 64 * 1024 * 1024;
+
+/// The UTF-8 byte order mark.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Number of leading bytes included in error previews.
+pub(super) const ERROR_PREVIEW_LEN: usize = 32;
+
+/// A size threshold that triggers a callback without failing the conversion.
+#[derive(Clone)]
+pub(super) struct SoftLimit {
+    pub(super) limit: usize,
+    pub(super) callback: Arc<dyn Fn(usize) + Send + Sync>,
+}
+
+/// A minimum throughput that a body must sustain over a sliding window.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct MinRate {
+    pub(super) bytes_per_sec: u64,
+    pub(super) window: Duration,
+}
+
+/// A per-chunk timeout that tolerates a limited number of stalls.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ChunkTimeout {
+    pub(super) base: Duration,
+    pub(super) allowed_stalls: usize,
+}
+
+/// Signature of the callback registered with `with_on_error`.
+type ErrorCallback = dyn Fn(&[u8], &StreamConverterError) + Send + Sync;
+
+/// A callback that receives the partially collected bytes when a conversion fails.
+#[derive(Clone)]
+pub(super) struct ErrorHook(Arc<ErrorCallback>);
+
+impl fmt::Debug for ErrorHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ErrorHook(..)")
+    }
+}
+
+impl fmt::Debug for SoftLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SoftLimit")
+            .field("limit", &self.limit)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StreamConverter {
+    /// Creates a new StreamConverter with the default buffer size (8KB).
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// 
+    /// let converter = StreamConverter::new();
+    /// ```
+    pub fn new() -> Self {
+        StreamConverterBuilder::new().build()
+    }
+
+    /// Creates a new StreamConverter with a custom buffer size.
+    /// 
+    /// A `buffer_size` of 0 is treated as 1; use `try_with_buffer_size` to
+    /// reject it instead.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `buffer_size` - The size of the internal buffer in bytes
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// 
+    /// let converter = StreamConverter::with_buffer_size(16384); // 16KB buffer
+    /// ```
+    pub fn with_buffer_size(buffer_size: usize) -> Self {
+        StreamConverterBuilder::new().buffer_size(buffer_size).build()
+    }
+
+    /// Creates a new StreamConverter with a custom buffer size, rejecting a size of 0.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `buffer_size` - The size of the internal buffer in bytes
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing the StreamConverter, or an `InvalidBufferSize` error
+    /// if `buffer_size` is 0
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// 
+    /// assert!(StreamConverter::try_with_buffer_size(16384).is_ok());
+    /// assert!(StreamConverter::try_with_buffer_size(0).is_err());
+    /// ```
+    pub fn try_with_buffer_size(buffer_size: usize) -> Result<Self, StreamConverterError> {
+        if buffer_size == 0 {
+            return Err(StreamConverterError::InvalidBufferSize { size: buffer_size });
+        }
+        Ok(Self::with_buffer_size(buffer_size))
+    }
+
+    /// Returns the size of the internal buffer in bytes.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// 
+    /// let converter = StreamConverter::with_buffer_size(16384);
+    /// assert_eq!(converter.buffer_size(), 16384);
+    /// ```
+    pub fn buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+
+    /// Returns a builder for configuring several options at once.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// 
+    /// let converter = StreamConverter::builder()
+    ///     .buffer_size(16384)
+    ///     .max_size(1024 * 1024)
+    ///     .build();
+    /// ```
+    pub fn builder() -> StreamConverterBuilder {
+        StreamConverterBuilder::new()
+    }
+
+    /// Sets a hard limit on the number of bytes a conversion may read.
+    /// 
+    /// The limit is checked as each chunk arrives, before it is appended, so
+    /// memory is never allocated past the cap. Conversions of larger bodies fail
+    /// with `StreamConverterError::SizeLimitExceeded`. By default there is no limit.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `limit` - The maximum body size in bytes
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// 
+    /// // Refuse bodies larger than 10MB
+    /// let converter = StreamConverter::new().with_max_size(10 * 1024 * 1024);
+    /// ```
+    pub fn with_max_size(mut self, limit: usize) -> Self {
+        self.max_size = Some(limit);
+        self
+    }
+
+    /// Sets a soft size limit that reports unusually large bodies without failing them.
+    /// 
+    /// The callback is invoked once per conversion, with the number of bytes read so far,
+    /// when the accumulated size first exceeds `limit`. Reading then continues as normal.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `limit` - The size in bytes above which the callback fires
+    /// * `callback` - Called with the accumulated size when the limit is crossed
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// 
+    /// let converter = StreamConverter::new()
+    ///     .with_soft_limit(1024 * 1024, |size| eprintln!("large body: {} bytes", size));
+    /// ```
+    pub fn with_soft_limit<F>(mut self, limit: usize, callback: F) -> Self
+    where
+        F: Fn(usize) + Send + Sync + 'static,
+    {
+        self.soft_limit = Some(SoftLimit {
+            limit,
+            callback: Arc::new(callback),
+        });
+        self
+    }
+
+    /// Sets how strictly a body's declared length is enforced.
+    /// 
+    /// See [`LengthCheck`] for the available modes. The default is
+    /// [`LengthCheck::Off`], which does not compare lengths.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// use b_stb::converter::LengthCheck;
+    /// 
+    /// let strict = StreamConverter::new().with_length_check(LengthCheck::Strict);
+    /// let lenient = StreamConverter::new().with_length_check(LengthCheck::warn(|expected, received| {
+    ///     eprintln!("expected {} bytes, received {}", expected, received);
+    /// }));
+    /// ```
+    pub fn with_length_check(mut self, check: LengthCheck) -> Self {
+        self.length_check = check;
+        self
+    }
+
+    /// Enables or disables lossy UTF-8 decoding.
+    /// 
+    /// When enabled, text conversions replace invalid UTF-8 sequences with
+    /// U+FFFD REPLACEMENT CHARACTER instead of failing with an `EncodingError`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// 
+    /// let converter = StreamConverter::new().with_lossy(true);
+    /// ```
+    pub fn with_lossy(mut self, enabled: bool) -> Self {
+        self.lossy = enabled;
+        self
+    }
+
+    /// Sets a callback that receives the partially collected bytes when `body_to_bytes` fails.
+    /// 
+    /// If the body fails mid-stream, the callback is invoked with the bytes
+    /// collected so far and the error, before the error is returned. This allows
+    /// partial data to be logged or cached without changing the method's result.
+    /// Because a converter can be reused, the callback is called once per failed
+    /// conversion.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// 
+    /// let converter = StreamConverter::new().with_on_error(|partial, error| {
+    ///     eprintln!("failed after {} bytes: {}", partial.len(), error);
+    /// });
+    /// ```
+    pub fn with_on_error<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&[u8], &StreamConverterError) + Send + Sync + 'static,
+    {
+        self.on_error = Some(ErrorHook(Arc::new(callback)));
+        self
+    }
+
+    /// Sets a minimum transfer rate that bodies must sustain.
+    /// 
+    /// Throughput is measured over a sliding window of length `window`. Once the
+    /// body has been read for at least one window, the conversion fails with
+    /// `StreamConverterError::TooSlow` if the average rate over the most recent
+    /// window is below `bytes_per_sec`. This also catches senders that stall
+    /// completely. A zero window disables the check.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `bytes_per_sec` - The minimum average rate in bytes per second
+    /// * `window` - The length of the window the rate is averaged over
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// use std::time::Duration;
+    /// 
+    /// // Require at least 1KB/s, averaged over 10 seconds
+    /// let converter = StreamConverter::new().with_min_rate(1024, Duration::from_secs(10));
+    /// ```
+    pub fn with_min_rate(mut self, bytes_per_sec: u64, window: Duration) -> Self {
+        self.min_rate = (!window.is_zero()).then_some(MinRate { bytes_per_sec, window });
+        self
+    }
+
+    /// Sets an overall timeout for reading a body.
+    /// 
+    /// The timeout covers the entire conversion, from the first read to the end
+    /// of the body, rather than each chunk. This stops slow-loris senders that
+    /// trickle data or hold a chunked connection open forever. When it expires
+    /// the conversion fails with `StreamConverterError::Timeout` and any data
+    /// collected so far is dropped. By default there is no timeout.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// use std::time::Duration;
+    /// 
+    /// let converter = StreamConverter::new().with_timeout(Duration::from_secs(30));
+    /// ```
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets a per-chunk timeout that tolerates occasional stalls.
+    /// 
+    /// Every `base` period spent waiting for a chunk without one arriving counts
+    /// as a stall. Up to `allowed_stalls` stalls are tolerated over the whole
+    /// conversion; the next one fails it with `StreamConverterError::Timeout`
+    /// carrying `base`. Bursty streams with occasional hiccups therefore succeed,
+    /// while a dead connection fails after at most `(allowed_stalls + 1) * base`.
+    /// By default there is no per-chunk timeout.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `base` - How long a single chunk wait may take before it counts as a stall
+    /// * `allowed_stalls` - The number of stalls tolerated before failing
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::time::Duration;
+    /// use b_stb::StreamConverter;
+    /// 
+    /// // Tolerate up to three 2-second hiccups
+    /// let converter = StreamConverter::new().with_chunk_timeout_jitter(Duration::from_secs(2), 3);
+    /// ```
+    pub fn with_chunk_timeout_jitter(mut self, base: Duration, allowed_stalls: usize) -> Self {
+        self.chunk_timeout = Some(ChunkTimeout { base, allowed_stalls });
+        self
+    }
+
+    /// Yields to the async scheduler after every `n_bytes` bytes processed.
+    /// 
+    /// Bodies that are already in memory (such as `Body::from` a large buffer)
+    /// never wait on I/O, so collecting them can monopolize the executor. With
+    /// this option the conversion calls `tokio::task::yield_now` periodically,
+    /// keeping other tasks responsive. Passing `0` disables yielding, which is
+    /// the default.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// 
+    /// // Yield after every 1MB processed
+    /// let converter = StreamConverter::new().with_yield_every(1024 * 1024);
+    /// ```
+    pub fn with_yield_every(mut self, n_bytes: usize) -> Self {
+        self.yield_every = (n_bytes > 0).then_some(n_bytes);
+        self
+    }
+
+    /// Sets how many chunks forwarding methods may read ahead of a slow consumer.
+    /// 
+    /// Methods that forward a body to a writer read and write concurrently
+    /// through a bounded queue of `n_chunks` chunks. Once the queue is full,
+    /// reading pauses until the consumer catches up, so Hyper's flow control
+    /// still applies instead of the body being buffered without limit. The
+    /// default is 1; passing `0` is treated as 1.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// 
+    /// // Allow up to 8 chunks in flight between the body and the writer
+    /// let converter = StreamConverter::new().with_forward_buffer(8);
+    /// ```
+    pub fn with_forward_buffer(mut self, n_chunks: usize) -> Self {
+        self.forward_buffer = n_chunks.max(1);
+        self
+    }
+
+    /// Enables or disables stripping a leading UTF-8 byte order mark.
+    /// 
+    /// When enabled, `body_to_string`, `to_string`, and their lossy variants
+    /// remove the bytes `EF BB BF` if, and only if, they are the very first bytes
+    /// of the content, even when they arrive split across chunks. Disabled by
+    /// default, which keeps the BOM as a leading `\u{FEFF}`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// 
+    /// let converter = StreamConverter::new().with_strip_bom(true);
+    /// ```
+    pub fn with_strip_bom(mut self, enabled: bool) -> Self {
+        self.strip_bom = enabled;
+        self
+    }
+
+    /// Enables or disables adapting the read buffer to the source.
+    /// 
+    /// When enabled, `to_bytes` and `to_string` start with the configured buffer
+    /// size, then double the buffer (up to 1 MiB) when reads consistently fill it
+    /// and halve it (down to 1 KiB) when reads consistently use a quarter of it
+    /// or less. If the configured size is outside those bounds, it becomes the
+    /// bound instead. Disabled by default, which keeps the buffer size fixed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// 
+    /// let converter = StreamConverter::new().with_adaptive_buffer(true);
+    /// ```
+    pub fn with_adaptive_buffer(mut self, enabled: bool) -> Self {
+        self.adaptive_buffer = enabled;
+        self
+    }
+
+    /// Bounds how many `body_to_bytes` conversions may run at once.
+    /// 
+    /// Each conversion acquires a permit from `semaphore` before reading and
+    /// holds it until the body has been collected, waiting while none is
+    /// available. Sharing one semaphore between converters (or clones of a
+    /// converter) applies a single limit across all of them. The wait for a
+    /// permit is not counted towards `with_timeout`. If the semaphore is closed,
+    /// conversions fail with an `IoError`. By default there is no limit.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use b_stb::StreamConverter;
+    /// use tokio::sync::Semaphore;
+    /// 
+    /// let converter = StreamConverter::new().with_concurrency_limit(Arc::new(Semaphore::new(4)));
+    /// ```
+    pub fn with_concurrency_limit(mut self, semaphore: Arc<Semaphore>) -> Self {
+        self.concurrency_limit = Some(semaphore);
+        self
+    }
+
+    /// Enables or disables hex previews in decoding errors.
+    /// 
+    /// When enabled, errors from decoding a collected body (such as invalid UTF-8)
+    /// are wrapped in `StreamConverterError::WithPreview`, whose `Display` output
+    /// includes the first 32 bytes of the data in hex.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// 
+    /// let converter = StreamConverter::new().with_error_preview(true);
+    /// ```
+    pub fn with_error_preview(mut self, enabled: bool) -> Self {
+        self.error_preview = enabled;
+        self
+    }
+
+    /// Sets how line streams treat a newline at the very end of the body.
+    /// 
+    /// See [`TrailingNewline`] for the available policies. The default is
+    /// [`TrailingNewline::Ignore`].
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// use b_stb::converter::TrailingNewline;
+    /// 
+    /// let converter = StreamConverter::new().with_trailing_newline(TrailingNewline::EmitEmpty);
+    /// ```
+    pub fn with_trailing_newline(mut self, policy: TrailingNewline) -> Self {
+        self.trailing_newline = policy;
+        self
+    }
+
+    /// Sets how `body_to_string` handles embedded NUL bytes.
+    /// 
+    /// See [`NulPolicy`] for the available policies. The default is
+    /// [`NulPolicy::Keep`], which leaves the body unchanged.
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust
+    /// use b_stb::StreamConverter;
+    /// use b_stb::converter::NulPolicy;
+    /// 
+    /// let converter = StreamConverter::new().with_nul_policy(NulPolicy::Strip);
+    /// ```
+    pub fn with_nul_policy(mut self, policy: NulPolicy) -> Self {
+        self.nul_policy = policy;
+        self
+    }
+
+    /// Returns a hex preview of the start of `data` if error previews are enabled.
+    pub(super) fn preview_of(&self, data: &[u8]) -> Option<String> {
+        if !self.error_preview {
+            return None;
+        }
+        let mut preview = data
+            .iter()
+            .take(ERROR_PREVIEW_LEN)
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if data.len() > ERROR_PREVIEW_LEN {
+            preview.push_str(" ...");
+        }
+        Some(preview)
+    }
+
+    /// Removes a leading UTF-8 BOM from collected bytes if BOM stripping is enabled.
+    pub(super) fn apply_strip_bom(&self, bytes: &mut Vec<u8>) {
+        if self.strip_bom && bytes.starts_with(UTF8_BOM) {
+            bytes.drain(..UTF8_BOM.len());
+        }
+    }
+
+    /// Decodes collected bytes as UTF-8, honouring the lossy setting and
+    /// attaching a preview to the error if enabled.
+    pub(super) fn decode_utf8(&self, bytes: Vec<u8>) -> Result<String, StreamConverterError> {
+        if self.lossy {
+            return Ok(into_string_lossy(bytes));
+        }
+        String::from_utf8(bytes).map_err(|e| self.encoding_error(e))
+    }
+
+    /// Wraps a UTF-8 error, attaching a preview if enabled.
+    pub(super) fn encoding_error(&self, error: FromUtf8Error) -> StreamConverterError {
+        let preview = self.preview_of(error.as_bytes());
+        StreamConverterError::EncodingError(error).with_preview(preview)
+    }
+
+    /// Creates a `ChunkReader` that reads `body` under this converter's configuration.
+    pub(super) fn chunks<B>(&self, body: B) -> ChunkReader<'_, B>
+    where
+        B: HttpBody + Unpin,
+        B::Error: Into<Box<dyn Error + Send + Sync>>,
+    {
+        ChunkReader::new(self, body)
+    }
+
+    /// Reads `body` into a bounded queue of `forward_buffer` chunks.
+    ///
+    /// The returned future reads the body and must be polled alongside the
+    /// consumer of the receiver. It stops after forwarding an error, or as soon
+    /// as the receiver is dropped.
+    pub(super) fn forward(
+        &self,
+        body: Body,
+    ) -> (impl Future<Output = ()> + '_, mpsc::Receiver<Result<Bytes, StreamConverterError>>) {
+        let (sender, receiver) = mpsc::channel(self.forward_buffer);
+        let mut chunks = self.chunks(body);
+        let producer = async move {
+            while let Some(chunk) = chunks.next_chunk().await {
+                let failed = chunk.is_err();
+                if sender.send(chunk).await.is_err() || failed {
+                    break;
+                }
+            }
+        };
+        (producer, receiver)
+    }
+
+    /// Converts a Hyper body into a String.
+    /// 
+    /// This method efficiently handles chunked transfer encoding and
+    /// automatically manages memory usage through streaming. Any
+    /// `http_body::Body` whose error converts into a boxed error is accepted,
+    /// not just `hyper::Body`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `body` - The Hyper response body (or other `http_body::Body`) to convert
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing either the converted String or a StreamConverterError
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from("Hello");
+    ///     
+    ///     let content = converter.body_to_string(body).await?;
+    ///     assert_eq!(content, "Hello");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_string<B>(&self, body: B) -> Result<String, StreamConverterError>
+    where
+        B: HttpBody + Unpin,
+        B::Error: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let mut bytes = self.body_to_bytes(body).await?;
+        self.apply_strip_bom(&mut bytes);
+        self.nul_policy.apply(&mut bytes)?;
+        self.decode_utf8(bytes)
+    }
+
+    /// Converts a Hyper body into a String, replacing invalid UTF-8 sequences.
+    /// 
+    /// Invalid sequences become U+FFFD REPLACEMENT CHARACTER, regardless of the
+    /// converter's `with_lossy` setting, so this method never returns an
+    /// `EncodingError`. Transport errors are still reported.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `body` - The Hyper response body to convert
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing either the converted String or a StreamConverterError
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from(vec![0xFF, 0x41]);
+    ///     
+    ///     let content = converter.body_to_string_lossy(body).await?;
+    ///     assert_eq!(content, "\u{FFFD}A");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_string_lossy(&self, body: Body) -> Result<String, StreamConverterError> {
+        let mut bytes = self.body_to_bytes(body).await?;
+        self.apply_strip_bom(&mut bytes);
+        self.nul_policy.apply(&mut bytes)?;
+        Ok(into_string_lossy(bytes))
+    }
+
+    /// Converts a Hyper body into a vector of bytes.
+    /// 
+    /// Any `http_body::Body` whose error converts into a boxed error is accepted,
+    /// not just `hyper::Body`. Errors from Hyper bodies are reported as
+    /// `HyperError`, and errors from other bodies as `BodyError`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `body` - The Hyper response body (or other `http_body::Body`) to convert
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing either the byte vector or a StreamConverterError
+    pub async fn body_to_bytes<B>(&self, body: B) -> Result<Vec<u8>, StreamConverterError>
+    where
+        B: HttpBody + Unpin,
+        B::Error: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let hint = usize::try_from(body.size_hint().lower()).unwrap_or(usize::MAX);
+        self.body_to_bytes_with_hint(body, hint.min(MAX_PREALLOCATION)).await
+    }
+
+    /// Converts a Hyper body borrowed mutably into a vector of bytes.
+    /// 
+    /// This reads the remaining data of a body that stays owned by the caller.
+    /// The body's borrow flag is set for the duration of the read; see
+    /// [`GuardedBody`] for what is checked.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `body` - The guarded Hyper response body to read
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing either the byte vector or a StreamConverterError
+    /// 
+    /// # Panics
+    /// 
+    /// In debug builds, if an earlier read of `body` was leaked while in progress
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust,no_run
+    /// use b_stb::converter::GuardedBody;
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let mut body = GuardedBody::new(Body::from("data"));
+    ///     
+    ///     let bytes = converter.body_to_bytes_borrowed(&mut body).await?;
+    ///     assert_eq!(bytes, b"data");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_borrowed(&self, body: &mut GuardedBody) -> Result<Vec<u8>, StreamConverterError> {
+        let read = body.begin_read();
+        self.body_to_bytes(&mut *read.inner).await
+    }
+
+    /// Converts a Hyper body into a vector of bytes, pre-allocating room for `size_hint` bytes.
+    /// 
+    /// When the body's size is known in advance, allocating once avoids the
+    /// repeated reallocation and copying of a growing vector. The allocation is
+    /// capped at the limit set with `with_max_size`, so an oversized hint cannot
+    /// force a giant allocation. `body_to_bytes` calls this automatically with the
+    /// body's own size hint (derived from `Content-Length`).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `body` - The Hyper response body (or other `http_body::Body`) to convert
+    /// * `size_hint` - The expected size of the body in bytes
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing either the byte vector or a StreamConverterError
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from(vec![0u8; 5 * 1024 * 1024]);
+    ///     
+    ///     let bytes = converter.body_to_bytes_with_hint(body, 5 * 1024 * 1024).await?;
+    ///     assert_eq!(bytes.len(), bytes.capacity());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_with_hint<B>(&self, body: B, size_hint: usize) -> Result<Vec<u8>, StreamConverterError>
+    where
+        B: HttpBody + Unpin,
+        B::Error: Into<Box<dyn Error + Send + Sync>>,
+    {
+        // Held until the body is collected, bounding concurrent conversions
+        let _permit = match &self.concurrency_limit {
+            Some(semaphore) => Some(
+                semaphore
+                    .acquire()
+                    .await
+                    .map_err(|e| StreamConverterError::IoError(io::Error::other(e)))?,
+            ),
+            None => None,
+        };
+        let capacity = self.max_size.map_or(size_hint, |limit| size_hint.min(limit));
+        let mut chunks = self.chunks(body);
+        let mut bytes = Vec::with_capacity(capacity);
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    if let Some(ErrorHook(callback)) = &self.on_error {
+                        callback(&bytes, &e);
+                    }
+                    return Err(e);
+                }
+            };
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(bytes)
+    }
+
+    /// Converts a Hyper body into a vector of bytes, XOR-ing each byte against a repeating key.
+    /// 
+    /// The position in the key carries across chunk boundaries, so the result is the same
+    /// regardless of how the body is chunked. An empty key leaves the bytes unchanged.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `body` - The Hyper response body to convert
+    /// * `key` - The key to XOR the body's bytes against
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing either the transformed bytes or a StreamConverterError
     /// 
     /// # Examples
     /// 
-    /// ```rust
+    /// ```rust,no_run
     /// use b_stb::StreamConverter;
+    /// use hyper::Body;
     /// 
-    /// let converter = StreamConverter::new();
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let bytes = converter.body_to_bytes_xored(Body::from(vec![0x01, 0x02]), &[0xFF]).await?;
+    ///     assert_eq!(bytes, vec![0xFE, 0xFD]);
+    ///     Ok(())
+    /// }
     /// ```
-    pub fn new() -> Self {
-        Self {
-            buffer_size: 8192 // Default 8KB buffer
+    pub async fn body_to_bytes_xored(&self, body: Body, key: &[u8]) -> Result<Vec<u8>, StreamConverterError> {
+        let mut chunks = self.chunks(body);
+        let mut bytes = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = chunk?;
+            if key.is_empty() {
+                bytes.extend_from_slice(&chunk);
+                continue;
+            }
+            let offset = bytes.len();
+            bytes.extend(
+                chunk
+                    .iter()
+                    .enumerate()
+                    .map(|(i, b)| b ^ key[(offset + i) % key.len()]),
+            );
         }
+        Ok(bytes)
     }
 
-    /// Creates a new StreamConverter with a custom buffer size.
+    /// Appends the bytes of a Hyper body to an existing `BytesMut` buffer.
+    /// 
+    /// This is intended for codec implementations (such as `tokio_util::codec` decoders)
+    /// that accumulate input in a `BytesMut`.
     /// 
     /// # Arguments
     /// 
-    /// * `buffer_size` - The size of the internal buffer in bytes
+    /// * `body` - The Hyper response body to convert
+    /// * `dst` - The buffer to append the body's bytes to
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing either the number of bytes appended or a StreamConverterError
     /// 
     /// # Examples
     /// 
-    /// ```rust
+    /// ```rust,no_run
     /// use b_stb::StreamConverter;
+    /// use bytes::BytesMut;
+    /// use hyper::Body;
     /// 
-    /// let converter = StreamConverter::with_buffer_size(16384); // 16KB buffer
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let mut dst = BytesMut::from("prefix:");
+    ///     
+    ///     let added = converter.body_to_bytes_mut(Body::from("data"), &mut dst).await?;
+    ///     assert_eq!(added, 4);
+    ///     assert_eq!(&dst[..], b"prefix:data");
+    ///     Ok(())
+    /// }
     /// ```
-    pub fn with_buffer_size(buffer_size: usize) -> Self {
-        Self { buffer_size }
+    pub async fn body_to_bytes_mut(&self, body: Body, dst: &mut BytesMut) -> Result<usize, StreamConverterError> {
+        let mut chunks = self.chunks(body);
+        let mut added = 0;
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = chunk?;
+            dst.extend_from_slice(&chunk);
+            added += chunk.len();
+        }
+        Ok(added)
     }
 
-    /// Converts a Hyper body into a String.
+    /// Fills a caller-provided buffer with the bytes of a Hyper body.
     /// 
-    /// This method efficiently handles chunked transfer encoding and
-    /// automatically manages memory usage through streaming.
+    /// No heap allocation is made for the body's contents. If the body does not
+    /// fit in `scratch`, a `SizeLimitExceeded` error is returned.
     /// 
     /// # Arguments
     /// 
     /// * `body` - The Hyper response body to convert
+    /// * `scratch` - The buffer to copy the body into
     /// 
     /// # Returns
     /// 
-    /// A Result containing either the converted String or a StreamConverterError
+    /// A Result containing the filled portion of `scratch`, or a StreamConverterError
     /// 
     /// # Examples
     /// 
@@ -86,35 +951,230 @@ impl StreamConverter {
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let converter = StreamConverter::new();
-    ///     let body = Body::from("Hello");
+    ///     let mut scratch = [0u8; 64];
     ///     
-    ///     let content = converter.body_to_string(body).await?;
-    ///     assert_eq!(content, "Hello");
+    ///     let data = converter.body_to_slice(Body::from("Hello"), &mut scratch).await?;
+    ///     assert_eq!(data, b"Hello");
     ///     Ok(())
     /// }
     /// ```
-    pub async fn body_to_string(&self, body: Body) -> Result<String, StreamConverterError> {
-        let bytes = self.body_to_bytes(body).await?;
-        String::from_utf8(bytes)
-            .map_err(StreamConverterError::EncodingError)
+    pub async fn body_to_slice<'a>(&self, body: Body, scratch: &'a mut [u8]) -> Result<&'a [u8], StreamConverterError> {
+        let mut chunks = self.chunks(body);
+        let mut filled = 0;
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = chunk?;
+            let end = filled + chunk.len();
+            if end > scratch.len() {
+                return Err(StreamConverterError::SizeLimitExceeded {
+                    limit: scratch.len(),
+                    read_so_far: end,
+                });
+            }
+            scratch[filled..end].copy_from_slice(&chunk);
+            filled = end;
+        }
+        Ok(&scratch[..filled])
     }
 
-    /// Converts a Hyper body into a vector of bytes.
+    /// Streams a Hyper body into an `AsyncWrite` sink.
+    /// 
+    /// Each chunk is written as it arrives from Hyper instead of being collected,
+    /// so peak memory stays at a few chunks rather than the whole body. Reading
+    /// runs ahead of the writer by at most the converter's forward buffer (see
+    /// `with_forward_buffer`). The writer is flushed once the body ends.
     /// 
     /// # Arguments
     /// 
-    /// * `body` - The Hyper response body to convert
+    /// * `body` - The Hyper response body to write
+    /// * `writer` - The writer that receives the body bytes
     /// 
     /// # Returns
     /// 
-    /// A Result containing either the byte vector or a StreamConverterError
-    pub async fn body_to_bytes(&self, mut body: Body) -> Result<Vec<u8>, StreamConverterError> {
-        let mut bytes = Vec::new();
-        while let Some(chunk) = body.next().await {
-            let chunk = chunk.map_err(StreamConverterError::HyperError)?;
-            bytes.extend_from_slice(&chunk);
+    /// A Result containing the number of bytes written, or a StreamConverterError
+    /// (an `IoError` if the writer fails)
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let mut file = tokio::fs::File::create("video.mp4").await?;
+    ///     
+    ///     let written = converter.body_to_writer(Body::from("..."), &mut file).await?;
+    ///     println!("Wrote {} bytes", written);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_writer<W>(&self, body: Body, writer: &mut W) -> Result<u64, StreamConverterError>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let (producer, chunks) = self.forward(body);
+        let consumer = async {
+            // Owning the receiver drops it on error, which stops the producer
+            let mut chunks = chunks;
+            let mut written = 0u64;
+            while let Some(chunk) = chunks.recv().await {
+                let chunk = chunk?;
+                writer.write_all(&chunk).await.map_err(StreamConverterError::IoError)?;
+                written += chunk.len() as u64;
+            }
+            writer.flush().await.map_err(StreamConverterError::IoError)?;
+            Ok(written)
+        };
+        let ((), written) = futures_util::future::join(producer, consumer).await;
+        written
+    }
+
+    /// Appends a Hyper response body to the end of a file, returning the number of bytes appended.
+    /// 
+    /// The file is opened in append mode (and created if it does not exist), so
+    /// a body fetched with a `Range: bytes=N-` header, where `N` is the file's
+    /// current size, resumes a partial download. The body is streamed as with
+    /// `body_to_writer`. If the body fails partway, the bytes received so far
+    /// remain in the file, so the download can be resumed again.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `body` - The Hyper response body to append
+    /// * `path` - The file to append to
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing the number of bytes appended, or a StreamConverterError
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::{Body, Client, Request};
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let path = "download.bin";
+    ///     let have = tokio::fs::metadata(path).await.map(|m| m.len()).unwrap_or(0);
+    /// 
+    ///     let req = Request::builder()
+    ///         .uri("http://example.com/large.bin")
+    ///         .header("Range", format!("bytes={}-", have))
+    ///         .body(Body::empty())?;
+    ///     let resp = Client::new().request(req).await?;
+    /// 
+    ///     let appended = converter.append_body_to_file(resp.into_body(), path).await?;
+    ///     println!("Resumed with {} more bytes", appended);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn append_body_to_file(
+        &self,
+        body: Body,
+        path: impl AsRef<Path>,
+    ) -> Result<u64, StreamConverterError> {
+        let mut file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .await
+            .map_err(StreamConverterError::IoError)?;
+        self.body_to_writer(body, &mut file).await
+    }
+
+    /// Streams a Hyper response body to standard output, returning the number of bytes written.
+    /// 
+    /// This covers the common `curl`-like case of piping a response to the
+    /// terminal. With `line_buffered`, output is flushed after every complete
+    /// line, even when a chunk holds several; otherwise it is flushed after
+    /// every chunk.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `body` - The Hyper response body to print
+    /// * `line_buffered` - Whether to flush per line rather than per chunk
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing the number of bytes written, or a StreamConverterError
+    /// (an `IoError` if writing to stdout fails)
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let written = converter.body_to_stdout(Body::from("Hello, World!\n"), true).await?;
+    ///     eprintln!("{} bytes", written);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_stdout(&self, body: Body, line_buffered: bool) -> Result<u64, StreamConverterError> {
+        let mut stdout = tokio::io::stdout();
+        let mut chunks = self.chunks(body);
+        let mut written = 0u64;
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = chunk?;
+            if line_buffered {
+                for line in chunk.split_inclusive(|&b| b == b'\n') {
+                    stdout.write_all(line).await.map_err(StreamConverterError::IoError)?;
+                    if line.ends_with(b"\n") {
+                        stdout.flush().await.map_err(StreamConverterError::IoError)?;
+                    }
+                }
+            } else {
+                stdout.write_all(&chunk).await.map_err(StreamConverterError::IoError)?;
+                stdout.flush().await.map_err(StreamConverterError::IoError)?;
+            }
+            written += chunk.len() as u64;
         }
-        Ok(bytes)
+        stdout.flush().await.map_err(StreamConverterError::IoError)?;
+        Ok(written)
+    }
+
+    /// Converts any stream of byte chunks into a vector of bytes.
+    /// 
+    /// This accepts sources that are not Hyper bodies, such as SDK download
+    /// streams or channels, and collects them exactly as `body_to_bytes` does,
+    /// honouring the converter's limits and timeouts. Errors from the stream are
+    /// reported as `BodyError` (or `HyperError` if the stream yields Hyper errors).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `stream` - A stream of `Result<Bytes, E>` chunks
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing either the byte vector or a StreamConverterError
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use bytes::Bytes;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let chunks = vec![Ok::<_, std::io::Error>(Bytes::from("Hello, ")), Ok(Bytes::from("World!"))];
+    ///     
+    ///     let bytes = converter.stream_to_bytes(futures_util::stream::iter(chunks)).await?;
+    ///     assert_eq!(bytes, b"Hello, World!");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn stream_to_bytes<S, E>(&self, stream: S) -> Result<Vec<u8>, StreamConverterError>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Unpin,
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        self.body_to_bytes(StreamBody::new(stream)).await
     }
 
     /// Converts an async reader into a String.
@@ -150,23 +1210,48 @@ impl StreamConverter {
     where
         R: AsyncRead + Unpin,
     {
-        let mut buffer = vec![0; self.buffer_size];
-        let mut result = String::new();
-
-        loop {
-            let bytes_read = reader.read(&mut buffer).await
-                .map_err(StreamConverterError::IoError)?;
-            
-            if bytes_read == 0 {
-                break;
-            }
-
-            let chunk = String::from_utf8(buffer[..bytes_read].to_vec())
-                .map_err(StreamConverterError::EncodingError)?;
-            result.push_str(&chunk);
-        }
+        // Decode once at the end so multibyte characters split across reads stay intact
+        let mut bytes = self.to_bytes(reader).await?;
+        self.apply_strip_bom(&mut bytes);
+        self.decode_utf8(bytes)
+    }
 
-        Ok(result)
+    /// Converts an async reader into a String, replacing invalid UTF-8 sequences.
+    /// 
+    /// This is the reader counterpart of `body_to_string_lossy`: it never returns
+    /// an `EncodingError`, but still reports I/O errors.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `reader` - Any async reader implementing AsyncRead + Unpin
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing either the converted String or a StreamConverterError
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use tokio::fs::File;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let mut file = File::open("scraped.html").await?;
+    ///     
+    ///     let content = converter.to_string_lossy(&mut file).await?;
+    ///     println!("File content: {}", content);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn to_string_lossy<R>(&self, reader: &mut R) -> Result<String, StreamConverterError>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut bytes = self.to_bytes(reader).await?;
+        self.apply_strip_bom(&mut bytes);
+        Ok(into_string_lossy(bytes))
     }
 
     /// Converts an async reader into a vector of bytes.
@@ -202,22 +1287,121 @@ impl StreamConverter {
     where
         R: AsyncRead + Unpin,
     {
-        let mut buffer = vec![0; self.buffer_size];
+        let mut buffer = ScratchBuffer::new(self);
+        let mut tracker = ReadTracker::new(self);
         let mut result = Vec::new();
 
         loop {
-            let bytes_read = reader.read(&mut buffer).await
+            tracker.maybe_yield().await;
+            let bytes_read = reader.read(buffer.as_mut_slice()).await
                 .map_err(StreamConverterError::IoError)?;
             
             if bytes_read == 0 {
                 break;
             }
+            tracker.record(bytes_read)?;
 
-            result.extend_from_slice(&buffer[..bytes_read]);
+            result.extend_from_slice(buffer.filled(bytes_read));
+            buffer.observe(bytes_read);
         }
 
         Ok(result)
     }
+
+    /// Converts a guarded async reader into a String.
+    /// 
+    /// This is `to_string` with the reader's borrow flag set for the duration of
+    /// the read; see [`GuardedBody`] for what is checked.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `reader` - Any guarded async reader implementing AsyncRead + Unpin
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing either the converted String or a StreamConverterError
+    /// 
+    /// # Panics
+    /// 
+    /// In debug builds, if an earlier read of `reader` was leaked while in progress
+    pub async fn to_string_borrowed<R>(&self, reader: &mut GuardedBody<R>) -> Result<String, StreamConverterError>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let read = reader.begin_read();
+        self.to_string(read.inner).await
+    }
+
+    /// Converts a guarded async reader into a String, replacing invalid UTF-8 sequences.
+    /// 
+    /// This is `to_string_lossy` with the reader's borrow flag set for the
+    /// duration of the read; see [`GuardedBody`] for what is checked.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `reader` - Any guarded async reader implementing AsyncRead + Unpin
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing either the converted String or a StreamConverterError
+    /// 
+    /// # Panics
+    /// 
+    /// In debug builds, if an earlier read of `reader` was leaked while in progress
+    pub async fn to_string_lossy_borrowed<R>(&self, reader: &mut GuardedBody<R>) -> Result<String, StreamConverterError>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let read = reader.begin_read();
+        self.to_string_lossy(read.inner).await
+    }
+
+    /// Converts a guarded async reader into a vector of bytes.
+    /// 
+    /// This is `to_bytes` with the reader's borrow flag set for the duration of
+    /// the read; see [`GuardedBody`] for what is checked.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `reader` - Any guarded async reader implementing AsyncRead + Unpin
+    /// 
+    /// # Returns
+    /// 
+    /// A Result containing either the byte vector or a StreamConverterError
+    /// 
+    /// # Panics
+    /// 
+    /// In debug builds, if an earlier read of `reader` was leaked while in progress
+    /// 
+    /// # Examples
+    /// 
+    /// ```rust,no_run
+    /// use b_stb::converter::GuardedBody;
+    /// use b_stb::StreamConverter;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let mut reader = GuardedBody::new(&b"data"[..]);
+    ///     
+    ///     let bytes = converter.to_bytes_borrowed(&mut reader).await?;
+    ///     assert_eq!(bytes, b"data");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn to_bytes_borrowed<R>(&self, reader: &mut GuardedBody<R>) -> Result<Vec<u8>, StreamConverterError>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let read = reader.begin_read();
+        self.to_bytes(read.inner).await
+    }
+}
+
+/// Converts bytes to a String, replacing invalid UTF-8 without copying valid input.
+fn into_string_lossy(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes)
+        .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
 }
 
 impl Default for StreamConverter {
@@ -0,0 +1,172 @@
+use hyper::Body;
+use sha2::{Digest, Sha256};
+
+use crate::error::StreamConverterError;
+use super::StreamConverter;
+
+/// Number of digest bytes included in an ETag.
+const ETAG_DIGEST_LEN: usize = 16;
+
+/// An in-progress SHA-256 computation that a body can be streamed into.
+///
+/// `HasherState` is useful for two-phase verification: the caller can feed
+/// additional data (a salt or prefix) into the hasher before the body, stream
+/// the body through it, append any suffix, and only then finalize the digest.
+///
+/// # Examples
+///
+/// ```rust
+/// use b_stb::converter::HasherState;
+///
+/// let mut state = HasherState::new().prefix(b"salt:");
+/// state.update(b"payload");
+/// let digest = state.finalize();
+/// assert_eq!(digest.len(), 32);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HasherState {
+    hasher: Sha256,
+}
+
+impl HasherState {
+    /// Creates a new, empty SHA-256 hasher state.
+    pub fn new() -> Self {
+        Self { hasher: Sha256::new() }
+    }
+
+    /// Feeds `data` into the hasher and returns the state, allowing a salt or
+    /// prefix to be chained onto [`HasherState::new`].
+    pub fn prefix(mut self, data: impl AsRef<[u8]>) -> Self {
+        self.update(data);
+        self
+    }
+
+    /// Feeds additional data into the hasher.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.hasher.update(data);
+    }
+
+    /// Returns a reference to the underlying `Sha256` hasher.
+    pub fn hasher(&self) -> &Sha256 {
+        &self.hasher
+    }
+
+    /// Consumes the state and returns the underlying `Sha256` hasher.
+    pub fn into_inner(self) -> Sha256 {
+        self.hasher
+    }
+
+    /// Consumes the state and returns the finalized digest bytes.
+    pub fn finalize(self) -> Vec<u8> {
+        self.hasher.finalize().to_vec()
+    }
+}
+
+impl StreamConverter {
+    /// Converts a Hyper body into a vector of bytes along with its SHA-256 digest.
+    ///
+    /// The digest is computed as chunks arrive, so the body is only read once.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the body bytes and the finalized digest bytes, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let (bytes, digest) = converter
+    ///         .body_to_bytes_with_hasher_state(Body::from("Hello"))
+    ///         .await?;
+    ///     assert_eq!(bytes, b"Hello");
+    ///     assert_eq!(digest.len(), 32);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_with_hasher_state(
+        &self,
+        body: Body,
+    ) -> Result<(Vec<u8>, Vec<u8>), StreamConverterError> {
+        let mut state = HasherState::new();
+        let bytes = self.body_to_bytes_hashed(body, &mut state).await?;
+        Ok((bytes, state.finalize()))
+    }
+
+    /// Converts a Hyper body into a vector of bytes along with a quoted ETag for its content.
+    ///
+    /// The ETag is the first 16 bytes of the body's SHA-256 digest in lowercase
+    /// hex, wrapped in double quotes (for example `"ba7816bf8f01cfea414140de5dae2223"` for `abc`),
+    /// ready to be sent in an `ETag` header or compared with `If-None-Match`.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the body bytes and the quoted ETag, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let (bytes, etag) = converter.body_to_bytes_with_etag(Body::from("content")).await?;
+    ///     assert!(etag.starts_with('"') && etag.ends_with('"'));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_with_etag(
+        &self,
+        body: Body,
+    ) -> Result<(Vec<u8>, String), StreamConverterError> {
+        let (bytes, digest) = self.body_to_bytes_with_hasher_state(body).await?;
+        let hex: String = digest[..ETAG_DIGEST_LEN]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        Ok((bytes, format!("\"{}\"", hex)))
+    }
+
+    /// Converts a Hyper body into a vector of bytes, feeding every chunk into
+    /// the provided [`HasherState`].
+    ///
+    /// The state is left unfinalized so callers can add a suffix before
+    /// calling [`HasherState::finalize`].
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    /// * `state` - The hasher state to update with the body bytes
+    ///
+    /// # Returns
+    ///
+    /// A Result containing either the byte vector or a StreamConverterError
+    pub async fn body_to_bytes_hashed(
+        &self,
+        body: Body,
+        state: &mut HasherState,
+    ) -> Result<Vec<u8>, StreamConverterError> {
+        let mut chunks = self.chunks(body);
+        let mut bytes = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = chunk?;
+            state.update(&chunk);
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(bytes)
+    }
+}
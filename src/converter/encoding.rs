@@ -0,0 +1,135 @@
+use encoding_rs::{CoderResult, Decoder, Encoding, UTF_8};
+use hyper::Body;
+
+use crate::error::StreamConverterError;
+use super::StreamConverter;
+
+/// Decodes `input` into `out`, growing `out` as needed.
+///
+/// Returns whether any malformed sequences were replaced.
+fn decode_into(decoder: &mut Decoder, input: &[u8], out: &mut String, last: bool) -> bool {
+    let mut had_replacements = false;
+    let mut input = input;
+    loop {
+        if let Some(needed) = decoder.max_utf8_buffer_length(input.len()) {
+            out.reserve(needed);
+        }
+        let (result, read, replaced) = decoder.decode_to_string(input, out, last);
+        had_replacements |= replaced;
+        input = &input[read..];
+        match result {
+            CoderResult::InputEmpty => return had_replacements,
+            CoderResult::OutputFull => out.reserve(input.len().max(4)),
+        }
+    }
+}
+
+/// Returns the encoding named by the `charset` parameter of a `Content-Type` value.
+fn charset_encoding(content_type: &str) -> Option<&'static Encoding> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        if !name.trim().eq_ignore_ascii_case("charset") {
+            return None;
+        }
+        Encoding::for_label(value.trim().trim_matches('"').as_bytes())
+    })
+}
+
+impl StreamConverter {
+    /// Converts a Hyper body into a String using the charset declared in a `Content-Type` header.
+    ///
+    /// The `charset` parameter (for example in `text/html; charset=windows-1251`)
+    /// is looked up with `encoding_rs`. When the header or parameter is absent,
+    /// or the charset is not recognized, the body is decoded as UTF-8. Malformed
+    /// sequences are replaced with U+FFFD REPLACEMENT CHARACTER, so the result
+    /// is always valid UTF-8.
+    ///
+    /// # Arguments
+    ///
+    /// * `content_type` - The value of the response's `Content-Type` header, if any
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the decoded String, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from(vec![0x63, 0x61, 0x66, 0xE9]);
+    ///
+    ///     let content = converter
+    ///         .body_to_string_with_charset(Some("text/plain; charset=windows-1252"), body)
+    ///         .await?;
+    ///     assert_eq!(content, "café");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_string_with_charset(
+        &self,
+        content_type: Option<&str>,
+        body: Body,
+    ) -> Result<String, StreamConverterError> {
+        let encoding = content_type.and_then(charset_encoding).unwrap_or(UTF_8);
+        let (content, _) = self.body_to_string_with_encoding(body, encoding).await?;
+        Ok(content)
+    }
+
+    /// Converts a Hyper body into a String using a caller-provided character encoding.
+    ///
+    /// The body is decoded with `encoding` as chunks arrive, bypassing any
+    /// header- or BOM-based detection; a BOM is decoded like any other bytes.
+    /// Malformed sequences are replaced with U+FFFD REPLACEMENT CHARACTER, and
+    /// the returned flag reports whether any replacement happened.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    /// * `encoding` - The `encoding_rs` encoding to decode the body with
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the decoded String and whether any malformed
+    /// sequences were replaced, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from(vec![0x63, 0x61, 0x66, 0xE9]);
+    ///
+    ///     let (content, had_errors) = converter
+    ///         .body_to_string_with_encoding(body, encoding_rs::WINDOWS_1252)
+    ///         .await?;
+    ///     assert_eq!(content, "café");
+    ///     assert!(!had_errors);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_string_with_encoding(
+        &self,
+        body: Body,
+        encoding: &'static Encoding,
+    ) -> Result<(String, bool), StreamConverterError> {
+        let mut decoder = encoding.new_decoder_without_bom_handling();
+        let mut chunks = self.chunks(body);
+        let mut content = String::new();
+        let mut had_replacements = false;
+        while let Some(chunk) = chunks.next_chunk().await {
+            had_replacements |= decode_into(&mut decoder, &chunk?, &mut content, false);
+        }
+        had_replacements |= decode_into(&mut decoder, &[], &mut content, true);
+        Ok((content, had_replacements))
+    }
+}
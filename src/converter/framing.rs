@@ -0,0 +1,245 @@
+use bytes::{Buf, Bytes, BytesMut};
+use futures_core::Stream;
+use futures_util::stream;
+use hyper::header::{CONTENT_LENGTH, TRANSFER_ENCODING};
+use hyper::{Body, HeaderMap, Response};
+
+use crate::error::StreamConverterError;
+use crate::util::find_subslice;
+use super::StreamConverter;
+
+/// How a response body was delimited on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Framing {
+    /// The body used chunked transfer encoding
+    Chunked,
+    /// The body was delimited by a `Content-Length` header with this value
+    ContentLength(usize),
+    /// Neither header was present (or valid), such as a body ending at connection close
+    Unknown,
+}
+
+impl Framing {
+    /// Determines the framing from a response's `Transfer-Encoding` and
+    /// `Content-Length` headers, with chunked encoding taking precedence.
+    fn from_headers(headers: &HeaderMap) -> Self {
+        let chunked = headers.get_all(TRANSFER_ENCODING).iter().any(|value| {
+            value
+                .to_str()
+                .is_ok_and(|value| value.split(',').any(|coding| coding.trim().eq_ignore_ascii_case("chunked")))
+        });
+        if chunked {
+            return Framing::Chunked;
+        }
+        headers
+            .get(CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+            .map_or(Framing::Unknown, Framing::ContentLength)
+    }
+}
+
+impl StreamConverter {
+    /// Splits a Hyper body into `Content-Length` framed messages, as used by
+    /// JSON-RPC transports such as the Language Server Protocol.
+    ///
+    /// Each message consists of a header block terminated by `\r\n\r\n` that must
+    /// contain a `Content-Length: N` header, followed by exactly `N` bytes of
+    /// payload. Other headers are ignored. Headers and payloads may span chunk
+    /// boundaries.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to split
+    ///
+    /// # Returns
+    ///
+    /// A stream yielding each message payload, or a StreamConverterError if the
+    /// body fails or is not correctly framed
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use futures_util::StreamExt;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from("Content-Length: 2\r\n\r\n{}");
+    ///
+    ///     let mut messages = Box::pin(converter.body_jsonrpc_messages(body));
+    ///     while let Some(message) = messages.next().await {
+    ///         println!("message: {:?}", message?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn body_jsonrpc_messages(
+        &self,
+        body: Body,
+    ) -> impl Stream<Item = Result<Vec<u8>, StreamConverterError>> + '_ {
+        let state = (self.chunks(body), BytesMut::new());
+        stream::try_unfold(state, |(mut chunks, mut buffer)| async move {
+            loop {
+                if let Some(message) = take_jsonrpc_message(&mut buffer)? {
+                    return Ok(Some((message, (chunks, buffer))));
+                }
+                match chunks.next_chunk().await {
+                    Some(chunk) => buffer.extend_from_slice(&chunk?),
+                    None if buffer.is_empty() => return Ok(None),
+                    None => {
+                        return Err(StreamConverterError::FramingError(
+                            "body ended in the middle of a message".to_string(),
+                        ))
+                    }
+                }
+            }
+        })
+    }
+
+    /// Parses a Hyper body as a sequence of length-type-value records.
+    ///
+    /// Each record is a big-endian `u32` payload length, a `u8` record type, and
+    /// then the payload itself. Payloads are returned as zero-copy slices of the
+    /// collected body, so records spanning chunk boundaries are handled naturally.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to parse
+    ///
+    /// # Returns
+    ///
+    /// A Result containing each record's type and payload in order, or a
+    /// `FramingError` if the body ends partway through a record
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from(vec![0, 0, 0, 2, 7, b'h', b'i']);
+    ///
+    ///     let records = converter.body_to_lvt_records(body).await?;
+    ///     assert_eq!(records[0].0, 7);
+    ///     assert_eq!(&records[0].1[..], b"hi");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_lvt_records(&self, body: Body) -> Result<Vec<(u8, Bytes)>, StreamConverterError> {
+        let mut chunks = self.chunks(body);
+        let mut collected = BytesMut::new();
+        while let Some(chunk) = chunks.next_chunk().await {
+            collected.extend_from_slice(&chunk?);
+        }
+
+        let mut data = collected.freeze();
+        let mut records = Vec::new();
+        while !data.is_empty() {
+            if data.len() < LVT_HEADER_LEN {
+                return Err(StreamConverterError::FramingError(format!(
+                    "truncated record header: {} of {} bytes",
+                    data.len(),
+                    LVT_HEADER_LEN
+                )));
+            }
+            let length = (&data[..4]).get_u32() as usize;
+            let record_type = data[4];
+            data.advance(LVT_HEADER_LEN);
+            if data.len() < length {
+                return Err(StreamConverterError::FramingError(format!(
+                    "truncated record payload: {} of {} bytes",
+                    data.len(),
+                    length
+                )));
+            }
+            records.push((record_type, data.split_to(length)));
+        }
+        Ok(records)
+    }
+
+    /// Converts a Hyper response body into bytes and reports how the body was framed.
+    ///
+    /// The framing is read from the response's `Transfer-Encoding` and
+    /// `Content-Length` headers before the body is collected, which makes it
+    /// possible to log how each response was delimited.
+    ///
+    /// # Arguments
+    ///
+    /// * `resp` - The Hyper response whose body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the bytes and their [`Framing`], or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use b_stb::converter::Framing;
+    /// use hyper::{Body, Response};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let resp = Response::builder()
+    ///         .header("Content-Length", "5")
+    ///         .body(Body::from("Hello"))?;
+    ///
+    ///     let (bytes, framing) = converter.body_to_bytes_with_framing(resp).await?;
+    ///     assert_eq!(bytes, b"Hello");
+    ///     assert_eq!(framing, Framing::ContentLength(5));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_with_framing(
+        &self,
+        resp: Response<Body>,
+    ) -> Result<(Vec<u8>, Framing), StreamConverterError> {
+        let framing = Framing::from_headers(resp.headers());
+        let bytes = self.body_to_bytes(resp.into_body()).await?;
+        Ok((bytes, framing))
+    }
+}
+
+/// Size of an LVT record header: a `u32` length followed by a `u8` type.
+const LVT_HEADER_LEN: usize = 5;
+
+/// Removes and returns the first complete message from `buffer`, if there is one.
+fn take_jsonrpc_message(buffer: &mut BytesMut) -> Result<Option<Vec<u8>>, StreamConverterError> {
+    let header_end = match find_subslice(buffer, b"\r\n\r\n") {
+        Some(pos) => pos,
+        None => return Ok(None),
+    };
+
+    let header = std::str::from_utf8(&buffer[..header_end]).map_err(|_| {
+        StreamConverterError::FramingError("message header is not valid UTF-8".to_string())
+    })?;
+    let mut content_length = None;
+    for line in header.split("\r\n") {
+        let (name, value) = line.split_once(':').ok_or_else(|| {
+            StreamConverterError::FramingError(format!("invalid header line: {:?}", line))
+        })?;
+        if name.trim().eq_ignore_ascii_case("content-length") {
+            let length = value.trim().parse::<usize>().map_err(|_| {
+                StreamConverterError::FramingError(format!("invalid Content-Length: {:?}", value.trim()))
+            })?;
+            content_length = Some(length);
+        }
+    }
+    let content_length = content_length.ok_or_else(|| {
+        StreamConverterError::FramingError("message header has no Content-Length".to_string())
+    })?;
+
+    let payload_start = header_end + 4;
+    if buffer.len() < payload_start + content_length {
+        return Ok(None);
+    }
+    let frame = buffer.split_to(payload_start + content_length);
+    Ok(Some(frame[payload_start..].to_vec()))
+}
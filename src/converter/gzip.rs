@@ -0,0 +1,429 @@
+use std::io::Write;
+
+use flate2::write::{GzDecoder, GzEncoder};
+use flate2::{Compression, Decompress, FlushDecompress, Status};
+use hyper::header::CONTENT_ENCODING;
+use hyper::{Body, HeaderMap, Response};
+
+use crate::error::StreamConverterError;
+use super::StreamConverter;
+
+/// A streaming decoder for a supported `Content-Encoding`.
+pub(super) enum ContentDecoder {
+    Gzip(GzDecoder<LimitedSink>),
+    Deflate(Inflater),
+}
+
+impl ContentDecoder {
+    /// Returns a decoder for the `Content-Encoding` in `headers`, or `None` if the
+    /// body is not encoded or uses an unsupported (or stacked) encoding.
+    ///
+    /// The decoder refuses to produce more than `limit` decompressed bytes.
+    pub(super) fn from_headers(headers: &HeaderMap, limit: Option<usize>) -> Option<(Self, String)> {
+        let encoding = headers.get(CONTENT_ENCODING)?.to_str().ok()?.trim().to_ascii_lowercase();
+        let sink = LimitedSink::new(limit);
+        let decoder = match encoding.as_str() {
+            "gzip" | "x-gzip" => ContentDecoder::Gzip(GzDecoder::new(sink)),
+            "deflate" => ContentDecoder::Deflate(Inflater::new(sink)),
+            _ => return None,
+        };
+        Some((decoder, encoding))
+    }
+
+    /// Feeds a chunk of compressed data into the decoder.
+    pub(super) fn write(&mut self, chunk: &[u8]) -> Result<(), StreamConverterError> {
+        let result = match self {
+            ContentDecoder::Gzip(decoder) => decoder.write_all(chunk),
+            ContentDecoder::Deflate(decoder) => decoder.write(chunk),
+        };
+        result.map_err(|e| self.sink().error(e))
+    }
+
+    /// Finishes decoding and returns the decompressed bytes.
+    ///
+    /// A stream that ends before its final block is reported as a
+    /// `DecompressionError` rather than returning the partial output.
+    pub(super) fn finish(mut self) -> Result<Vec<u8>, StreamConverterError> {
+        let result = match &mut self {
+            ContentDecoder::Gzip(decoder) => decoder.try_finish(),
+            ContentDecoder::Deflate(decoder) => decoder.try_finish(),
+        };
+        result.map_err(|e| self.sink().error(e))?;
+        match self {
+            ContentDecoder::Gzip(decoder) => decoder
+                .finish()
+                .map(|sink| sink.bytes)
+                .map_err(StreamConverterError::DecompressionError),
+            ContentDecoder::Deflate(decoder) => Ok(decoder.sink.bytes),
+        }
+    }
+
+    /// Returns the sink receiving the decompressed bytes.
+    fn sink(&self) -> &LimitedSink {
+        match self {
+            ContentDecoder::Gzip(decoder) => decoder.get_ref(),
+            ContentDecoder::Deflate(decoder) => &decoder.sink,
+        }
+    }
+}
+
+/// Size of the buffer `Inflater` decompresses into before passing output to its sink.
+const INFLATE_BUFFER_SIZE: usize = 32 * 1024;
+
+/// A streaming decoder for the `deflate` encoding.
+///
+/// The encoding is meant to be a zlib stream, but some servers send raw
+/// deflate data instead, so the format is chosen from the first two bytes:
+/// a valid zlib header selects zlib, anything else raw deflate.
+pub(super) struct Inflater {
+    /// The decompressor, created once the first two bytes have been seen
+    state: Option<Decompress>,
+    /// Bytes received before the format was known
+    head: Vec<u8>,
+    buffer: Vec<u8>,
+    sink: LimitedSink,
+    /// Whether the final block of the stream has been decoded
+    done: bool,
+}
+
+impl Inflater {
+    fn new(sink: LimitedSink) -> Self {
+        Self {
+            state: None,
+            head: Vec::new(),
+            buffer: vec![0; INFLATE_BUFFER_SIZE],
+            sink,
+            done: false,
+        }
+    }
+
+    /// Returns true if `head` starts with a valid zlib header (RFC 1950).
+    fn is_zlib_header(head: &[u8]) -> bool {
+        match head {
+            [cmf, flg, ..] => cmf & 0x0F == 8 && cmf >> 4 <= 7 && (u16::from(*cmf) << 8 | u16::from(*flg)) % 31 == 0,
+            _ => false,
+        }
+    }
+
+    /// Decompresses a chunk of input; data after the end of the stream is ignored.
+    fn write(&mut self, chunk: &[u8]) -> std::io::Result<()> {
+        if self.state.is_none() {
+            self.head.extend_from_slice(chunk);
+            if self.head.len() < 2 {
+                return Ok(());
+            }
+            let head = std::mem::take(&mut self.head);
+            self.state = Some(Decompress::new(Self::is_zlib_header(&head)));
+            return self.run(&head, FlushDecompress::None);
+        }
+        self.run(chunk, FlushDecompress::None)
+    }
+
+    /// Decodes any buffered input and fails if the stream did not reach its final block.
+    fn try_finish(&mut self) -> std::io::Result<()> {
+        if self.state.is_none() {
+            if self.head.is_empty() {
+                // An empty body has nothing to decode
+                return Ok(());
+            }
+            let head = std::mem::take(&mut self.head);
+            self.state = Some(Decompress::new(Self::is_zlib_header(&head)));
+            self.run(&head, FlushDecompress::None)?;
+        }
+        self.run(&[], FlushDecompress::Finish)?;
+        if self.done {
+            Ok(())
+        } else {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "deflate stream ended before its final block",
+            ))
+        }
+    }
+
+    /// Feeds `input` to the decompressor, passing all output to the sink.
+    fn run(&mut self, mut input: &[u8], flush: FlushDecompress) -> std::io::Result<()> {
+        let Some(state) = &mut self.state else {
+            return Ok(());
+        };
+        while !self.done {
+            let (in_before, out_before) = (state.total_in(), state.total_out());
+            let status = state.decompress(input, &mut self.buffer, flush)?;
+            let consumed = (state.total_in() - in_before) as usize;
+            let produced = (state.total_out() - out_before) as usize;
+            self.sink.write_all(&self.buffer[..produced])?;
+            input = &input[consumed..];
+            self.done = status == Status::StreamEnd;
+            // Stop once no progress is possible, unless the buffer filled and more output is pending
+            if produced < self.buffer.len() && (input.is_empty() || consumed == 0) {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A buffer for decompressed bytes that fails writes once it would grow past a limit.
+///
+/// The limit is enforced as output is produced, so a small, highly compressed
+/// chunk cannot expand far beyond the limit before it is detected.
+pub(super) struct LimitedSink {
+    bytes: Vec<u8>,
+    limit: Option<usize>,
+    exceeded_at: Option<usize>,
+}
+
+impl LimitedSink {
+    fn new(limit: Option<usize>) -> Self {
+        Self {
+            bytes: Vec::new(),
+            limit,
+            exceeded_at: None,
+        }
+    }
+
+    /// Converts a failure from the decoder into a StreamConverterError, reporting
+    /// a `SizeLimitExceeded` if the sink refused the output.
+    fn error(&self, e: std::io::Error) -> StreamConverterError {
+        match (self.limit, self.exceeded_at) {
+            (Some(limit), Some(read_so_far)) => StreamConverterError::SizeLimitExceeded { limit, read_so_far },
+            _ => StreamConverterError::DecompressionError(e),
+        }
+    }
+}
+
+impl Write for LimitedSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let total = self.bytes.len() + buf.len();
+        if self.limit.is_some_and(|limit| total > limit) {
+            self.exceeded_at = Some(total);
+            return Err(std::io::Error::other("decompressed size limit exceeded"));
+        }
+        self.bytes.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A writer that discards its input and only counts the bytes written to it.
+#[derive(Default)]
+struct ByteCounter(usize);
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl StreamConverter {
+    /// Converts a Hyper body into gzip-compressed bytes.
+    ///
+    /// Chunks are compressed as they arrive, so the uncompressed body is never
+    /// held in memory as a whole.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to compress
+    /// * `level` - The compression level, from 0 (none) to 9 (best); higher values are treated as 9
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the gzip-compressed bytes, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let compressed = converter.body_to_gzip(Body::from("Hello, World!"), 6).await?;
+    ///     assert_eq!(&compressed[..2], &[0x1f, 0x8b]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_gzip(&self, body: Body, level: u32) -> Result<Vec<u8>, StreamConverterError> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::new(level.min(9)));
+        let mut chunks = self.chunks(body);
+        while let Some(chunk) = chunks.next_chunk().await {
+            encoder
+                .write_all(&chunk?)
+                .map_err(StreamConverterError::IoError)?;
+        }
+        encoder.finish().map_err(StreamConverterError::IoError)
+    }
+
+    /// Converts a Hyper body into bytes and reports the size it would have when
+    /// gzip-compressed.
+    ///
+    /// Chunks are compressed at the default level into a sink that only counts
+    /// output bytes, so the compression ratio can be judged (for example, to
+    /// decide whether to store a payload compressed) without keeping a
+    /// compressed copy.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the raw bytes and their gzip-compressed length, or a
+    /// StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from("a".repeat(4096));
+    ///
+    ///     let (bytes, compressed_size) = converter.body_to_bytes_with_compressed_size(body).await?;
+    ///     println!("ratio: {:.2}", compressed_size as f64 / bytes.len() as f64);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_with_compressed_size(
+        &self,
+        body: Body,
+    ) -> Result<(Vec<u8>, usize), StreamConverterError> {
+        let mut encoder = GzEncoder::new(ByteCounter::default(), Compression::default());
+        let mut chunks = self.chunks(body);
+        let mut bytes = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = chunk?;
+            encoder
+                .write_all(&chunk)
+                .map_err(StreamConverterError::IoError)?;
+            bytes.extend_from_slice(&chunk);
+        }
+        let counter = encoder.finish().map_err(StreamConverterError::IoError)?;
+        Ok((bytes, counter.0))
+    }
+
+    /// Converts a Hyper response body into bytes, decompressing it according to
+    /// its `Content-Encoding` and reporting whether a decoder was applied.
+    ///
+    /// `gzip` (or `x-gzip`) and `deflate` bodies are decompressed as chunks arrive;
+    /// `deflate` accepts both zlib-wrapped and raw deflate data, and a truncated
+    /// stream is a `DecompressionError`. Any other encoding, including stacked encodings such as `gzip, br`, is
+    /// passed through untouched. The limit set with `with_max_size` applies to
+    /// the decompressed output as well as the compressed input, so a small,
+    /// highly compressed body cannot expand past it.
+    ///
+    /// # Arguments
+    ///
+    /// * `resp` - The Hyper response whose body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the bytes and `Some(encoding)` if they were decompressed
+    /// or `None` if they were passed through, or a StreamConverterError (a
+    /// `SizeLimitExceeded` if the decompressed output exceeds the size limit)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::{Body, Response};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let resp = Response::new(Body::from("plain"));
+    ///
+    ///     let (bytes, encoding) = converter.body_to_bytes_decoded_report(resp).await?;
+    ///     assert_eq!(bytes, b"plain");
+    ///     assert_eq!(encoding, None);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_decoded_report(
+        &self,
+        resp: Response<Body>,
+    ) -> Result<(Vec<u8>, Option<String>), StreamConverterError> {
+        let (parts, body) = resp.into_parts();
+        match ContentDecoder::from_headers(&parts.headers, self.max_size) {
+            Some((decoder, encoding)) => Ok((self.decode_with(decoder, body).await?, Some(encoding))),
+            None => Ok((self.body_to_bytes(body).await?, None)),
+        }
+    }
+
+    /// Converts a Hyper body into bytes, transparently decompressing it according
+    /// to the `Content-Encoding` in `headers`.
+    ///
+    /// `gzip` (or `x-gzip`) and `deflate` bodies are inflated as chunks arrive.
+    /// `deflate` bodies may be zlib-wrapped or, as some servers send them, raw
+    /// deflate data; a stream that ends early fails with a `DecompressionError`
+    /// instead of returning partial output. Unknown encodings, and bodies without a `Content-Encoding`, are returned
+    /// untouched. The limit set with `with_max_size` also bounds the inflated
+    /// output, which guards against decompression bombs.
+    ///
+    /// # Arguments
+    ///
+    /// * `headers` - The response headers, typically from `Response::into_parts`
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the decoded bytes, a `DecompressionError` if the
+    /// compressed data is malformed, or a `SizeLimitExceeded` if the inflated
+    /// output exceeds the size limit
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::{Body, Client};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let resp = Client::new().get("http://example.com".parse()?).await?;
+    ///     let (parts, body) = resp.into_parts();
+    ///
+    ///     let bytes = converter.decode_body(&parts.headers, body).await?;
+    ///     println!("Decoded {} bytes", bytes.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "compression")]
+    pub async fn decode_body(
+        &self,
+        headers: &HeaderMap,
+        body: Body,
+    ) -> Result<Vec<u8>, StreamConverterError> {
+        match ContentDecoder::from_headers(headers, self.max_size) {
+            Some((decoder, _)) => self.decode_with(decoder, body).await,
+            None => self.body_to_bytes(body).await,
+        }
+    }
+
+    /// Streams `body` through `decoder` and returns the decompressed bytes.
+    ///
+    /// The decoder enforces the size limit on its output as it inflates.
+    pub(super) async fn decode_with(
+        &self,
+        mut decoder: ContentDecoder,
+        body: Body,
+    ) -> Result<Vec<u8>, StreamConverterError> {
+        let mut chunks = self.chunks(body);
+        while let Some(chunk) = chunks.next_chunk().await {
+            decoder.write(&chunk?)?;
+        }
+        decoder.finish()
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, Read};
+use std::pin::pin;
+
+use bytes::{Buf, Bytes};
+use futures_util::future::{select, Either};
+use hyper::Body;
+use serde::de::{DeserializeOwned, Deserializer, IgnoredAny, MapAccess, Visitor};
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+use crate::error::StreamConverterError;
+use super::convert::ERROR_PREVIEW_LEN;
+use super::StreamConverter;
+
+/// A blocking reader over chunks sent from an async task.
+///
+/// Reading reaches end of input once the sending side is dropped.
+struct ChannelReader {
+    receiver: mpsc::Receiver<Bytes>,
+    current: Bytes,
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.current.is_empty() {
+            match self.receiver.blocking_recv() {
+                Some(chunk) => self.current = chunk,
+                None => return Ok(0),
+            }
+        }
+        let len = buf.len().min(self.current.len());
+        self.current.copy_to_slice(&mut buf[..len]);
+        Ok(len)
+    }
+}
+
+/// Collects the requested top-level fields of a JSON object, skipping the rest.
+struct FieldsVisitor<'k> {
+    keys: &'k [&'k str],
+}
+
+impl<'de> Visitor<'de> for FieldsVisitor<'_> {
+    type Value = HashMap<String, Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a JSON object")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut fields = HashMap::new();
+        while let Some(key) = map.next_key::<String>()? {
+            if self.keys.contains(&key.as_str()) {
+                let value = map.next_value::<Value>()?;
+                fields.insert(key, value);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(fields)
+    }
+}
+
+impl StreamConverter {
+    /// Converts a Hyper body into a value deserialized from JSON.
+    ///
+    /// The body is collected in full, subject to any limit set with
+    /// [`StreamConverter::with_max_size`], before being decoded.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing either the deserialized value or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from("[1, 2, 3]");
+    ///
+    ///     let values: Vec<u32> = converter.body_to_json(body).await?;
+    ///     assert_eq!(values, vec![1, 2, 3]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_json<T>(&self, body: Body) -> Result<T, StreamConverterError>
+    where
+        T: DeserializeOwned,
+    {
+        let bytes = self.body_to_bytes(body).await?;
+        serde_json::from_slice(&bytes).map_err(|e| {
+            StreamConverterError::JsonError(e).with_preview(self.preview_of(&bytes))
+        })
+    }
+
+    /// Converts a Hyper body into the first JSON value it contains, plus any bytes
+    /// that follow it.
+    ///
+    /// This supports payloads that concatenate a JSON value with other data
+    /// without any length framing. Parsing stops right after the first value, and
+    /// everything after it, including any whitespace, is returned untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the deserialized value and the remaining bytes, or a
+    /// StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from("[1, 2]\x00binary tail");
+    ///
+    ///     let (values, rest): (Vec<u32>, _) = converter.body_to_json_head(body).await?;
+    ///     assert_eq!(values, vec![1, 2]);
+    ///     assert_eq!(rest, b"\x00binary tail");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_json_head<T>(&self, body: Body) -> Result<(T, Vec<u8>), StreamConverterError>
+    where
+        T: DeserializeOwned,
+    {
+        let bytes = self.body_to_bytes(body).await?;
+        let mut values = serde_json::Deserializer::from_slice(&bytes).into_iter::<T>();
+        let value = match values.next() {
+            Some(Ok(value)) => value,
+            Some(Err(e)) => {
+                return Err(StreamConverterError::JsonError(e).with_preview(self.preview_of(&bytes)))
+            }
+            None => {
+                return Err(StreamConverterError::JsonError(serde::de::Error::custom(
+                    "body contains no JSON value",
+                )))
+            }
+        };
+        let rest = bytes[values.byte_offset()..].to_vec();
+        Ok((value, rest))
+    }
+
+    /// Checks whether a Hyper body is valid JSON without deserializing it into a type.
+    ///
+    /// The body is collected and validated with `serde::de::IgnoredAny`, which is
+    /// cheaper than building a `serde_json::Value`. Parse failures produce
+    /// `Ok(false)`; only failures to read the body are returned as errors.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to validate
+    ///
+    /// # Returns
+    ///
+    /// A Result containing whether the body is valid JSON, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     assert!(converter.body_is_valid_json(Body::from(r#"{"ok": true}"#)).await?);
+    ///     assert!(!converter.body_is_valid_json(Body::from("{oops")).await?);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_is_valid_json(&self, body: Body) -> Result<bool, StreamConverterError> {
+        let bytes = self.body_to_bytes(body).await?;
+        Ok(serde_json::from_slice::<IgnoredAny>(&bytes).is_ok())
+    }
+
+    /// Extracts selected top-level fields from a JSON object body.
+    ///
+    /// The object is parsed incrementally as chunks arrive, on a blocking task fed
+    /// one chunk at a time, so the raw body is never collected. Only the values of
+    /// the requested keys are built into `serde_json::Value`s; every other field is
+    /// validated and skipped without being materialized, which bounds memory for
+    /// large objects with unwanted large fields. Malformed JSON is reported as soon
+    /// as it is seen, without waiting for the rest of the body. Keys that do not
+    /// appear in the object are absent from the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to parse
+    /// * `keys` - The top-level keys to extract
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a map of the extracted fields, or a `JsonError` if the
+    /// body is not a valid JSON object
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from(r#"{"id": 7, "blob": [1, 2, 3], "name": "demo"}"#);
+    ///
+    ///     let fields = converter.body_json_fields(body, &["id", "name"]).await?;
+    ///     assert_eq!(fields["id"], 7);
+    ///     assert!(!fields.contains_key("blob"));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_json_fields(
+        &self,
+        body: Body,
+        keys: &[&str],
+    ) -> Result<HashMap<String, Value>, StreamConverterError> {
+        let keys: Vec<String> = keys.iter().map(ToString::to_string).collect();
+        let (sender, receiver) = mpsc::channel(1);
+        let parser = tokio::task::spawn_blocking(move || {
+            let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+            let reader = ChannelReader {
+                receiver,
+                current: Bytes::new(),
+            };
+            let mut deserializer = serde_json::Deserializer::from_reader(reader);
+            let fields = deserializer.deserialize_map(FieldsVisitor { keys: &keys })?;
+            deserializer.end()?;
+            Ok::<_, serde_json::Error>(fields)
+        });
+
+        // Only the leading bytes are kept, for error previews
+        let mut head = Vec::new();
+        let mut chunks = self.chunks(body);
+        loop {
+            // The parser only stops before the end of the body on malformed input
+            let next = match select(pin!(chunks.next_chunk()), pin!(sender.closed())).await {
+                Either::Left((next, _)) => next,
+                Either::Right(_) => break,
+            };
+            let Some(chunk) = next else {
+                break;
+            };
+            let chunk = chunk?;
+            if self.error_preview && head.len() <= ERROR_PREVIEW_LEN {
+                head.extend_from_slice(&chunk[..chunk.len().min(ERROR_PREVIEW_LEN + 1 - head.len())]);
+            }
+            if sender.send(chunk).await.is_err() {
+                break;
+            }
+        }
+        drop(sender);
+
+        parser
+            .await
+            .map_err(|e| StreamConverterError::IoError(e.into()))?
+            .map_err(|e| StreamConverterError::JsonError(e).with_preview(self.preview_of(&head)))
+    }
+}
@@ -1,2 +1,40 @@
+mod binary;
+mod builder;
+#[cfg(feature = "cbor")]
+mod cbor;
+mod concurrent;
 mod convert;
-pub use convert::StreamConverter; 
\ No newline at end of file
+#[cfg(feature = "encoding")]
+mod encoding;
+mod framing;
+#[cfg(feature = "gzip")]
+mod gzip;
+#[cfg(feature = "json")]
+mod json;
+mod multipart;
+mod progress;
+mod reader;
+#[cfg(feature = "reqwest")]
+mod reqwest;
+mod retry;
+mod sse;
+#[cfg(feature = "tempfile")]
+mod spool;
+mod streaming;
+mod text;
+mod transform;
+#[cfg(feature = "digest")]
+mod digest;
+
+pub use binary::{ByteClasses, Compression, ReadOutcome};
+pub use builder::StreamConverterBuilder;
+pub use convert::StreamConverter;
+pub use framing::Framing;
+pub use multipart::Part;
+pub use reader::{GuardedBody, LengthCheck};
+pub use sse::SseEvent;
+pub use streaming::StreamingResponse;
+pub use text::{LineEnding, NulPolicy, TrailingNewline};
+pub use transform::ChunkTransform;
+#[cfg(feature = "digest")]
+pub use digest::HasherState;
@@ -0,0 +1,229 @@
+use bytes::Bytes;
+use hyper::Body;
+
+use crate::error::StreamConverterError;
+use crate::util::{find_subslice, parse_header_block};
+use super::StreamConverter;
+
+/// A single part parsed from a `multipart/*` body.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Part {
+    /// The part's headers, in order
+    pub headers: Vec<(String, String)>,
+    /// The `name` parameter of the `Content-Disposition` header, if present
+    pub name: Option<String>,
+    /// The file name from the `Content-Disposition` header, if present
+    ///
+    /// An RFC 5987 `filename*` parameter takes precedence over a plain `filename`.
+    pub filename: Option<String>,
+    /// The `Content-Type` header, if present
+    pub content_type: Option<String>,
+    /// The raw content of the part
+    pub body: Bytes,
+}
+
+impl Part {
+    /// Builds a part from its header block and content.
+    fn new(headers: Vec<(String, String)>, body: Bytes) -> Self {
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.clone())
+        };
+        let content_type = header("Content-Type");
+        let (name, filename) = match header("Content-Disposition") {
+            Some(disposition) => parse_disposition(&disposition),
+            None => (None, None),
+        };
+        Self {
+            headers,
+            name,
+            filename,
+            content_type,
+            body,
+        }
+    }
+}
+
+/// Extracts the `name` and file name parameters from a `Content-Disposition` value.
+fn parse_disposition(value: &str) -> (Option<String>, Option<String>) {
+    let mut name = None;
+    let mut filename = None;
+    let mut extended_filename = None;
+    for (key, value) in disposition_params(value) {
+        match key.to_ascii_lowercase().as_str() {
+            "name" => name = Some(value),
+            "filename" => filename = Some(value),
+            "filename*" => extended_filename = decode_ext_value(&value),
+            _ => {}
+        }
+    }
+    (name, extended_filename.or(filename))
+}
+
+/// Splits the `key=value` parameters following the disposition type, unquoting
+/// quoted values.
+fn disposition_params(value: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut rest = match value.split_once(';') {
+        Some((_, rest)) => rest,
+        None => return params,
+    };
+    loop {
+        rest = rest.trim_start_matches([' ', '\t', ';']);
+        let Some((key, after)) = rest.split_once('=') else {
+            return params;
+        };
+        let key = key.trim().to_string();
+        let after = after.trim_start();
+        let (value, remaining) = match after.strip_prefix('"') {
+            Some(quoted) => unquote(quoted),
+            None => {
+                let end = after.find(';').unwrap_or(after.len());
+                (after[..end].trim_end().to_string(), &after[end..])
+            }
+        };
+        params.push((key, value));
+        rest = remaining;
+    }
+}
+
+/// Reads a quoted string (after its opening quote), resolving backslash escapes.
+///
+/// Returns the unquoted value and the input following the closing quote.
+fn unquote(quoted: &str) -> (String, &str) {
+    let mut value = String::new();
+    let mut chars = quoted.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some((_, escaped)) = chars.next() {
+                    value.push(escaped);
+                }
+            }
+            '"' => return (value, &quoted[i + 1..]),
+            c => value.push(c),
+        }
+    }
+    (value, "")
+}
+
+/// Decodes an RFC 5987 extended value such as `UTF-8''na%C3%AFve.txt`.
+///
+/// Only the UTF-8 and ISO-8859-1 charsets are supported.
+fn decode_ext_value(value: &str) -> Option<String> {
+    let mut fields = value.splitn(3, '\'');
+    let charset = fields.next()?;
+    let _language = fields.next()?;
+    let encoded = fields.next()?;
+
+    let mut bytes = Vec::with_capacity(encoded.len());
+    let mut input = encoded.bytes();
+    while let Some(b) = input.next() {
+        if b == b'%' {
+            let hex = [input.next()?, input.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(b);
+        }
+    }
+
+    if charset.eq_ignore_ascii_case("UTF-8") {
+        String::from_utf8(bytes).ok()
+    } else if charset.eq_ignore_ascii_case("ISO-8859-1") {
+        Some(bytes.into_iter().map(char::from).collect())
+    } else {
+        None
+    }
+}
+
+/// Splits a complete multipart body into its parts.
+fn parse_multipart(bytes: Bytes, boundary: &str) -> Result<Vec<Part>, StreamConverterError> {
+    let delimiter = format!("--{}", boundary);
+    let close_delimiter = format!("\r\n{}", delimiter);
+
+    let mut pos = find_subslice(&bytes, delimiter.as_bytes())
+        .ok_or_else(|| StreamConverterError::FramingError("missing multipart boundary".to_string()))?
+        + delimiter.len();
+    let mut parts = Vec::new();
+    loop {
+        if bytes[pos..].starts_with(b"--") {
+            return Ok(parts);
+        }
+        // Transport padding may follow a boundary before its line break
+        while bytes.get(pos).is_some_and(|&b| b == b' ' || b == b'\t') {
+            pos += 1;
+        }
+        if !bytes[pos..].starts_with(b"\r\n") {
+            return Err(StreamConverterError::FramingError(
+                "expected a line break after the multipart boundary".to_string(),
+            ));
+        }
+        pos += 2;
+
+        let (headers, body_start) = if bytes[pos..].starts_with(b"\r\n") {
+            (Vec::new(), pos + 2)
+        } else {
+            let end = find_subslice(&bytes[pos..], b"\r\n\r\n").ok_or_else(|| {
+                StreamConverterError::FramingError("unterminated multipart part headers".to_string())
+            })?;
+            (parse_header_block(&bytes[pos..pos + end])?, pos + end + 4)
+        };
+
+        let body_len = find_subslice(&bytes[body_start..], close_delimiter.as_bytes()).ok_or_else(|| {
+            StreamConverterError::FramingError("missing closing multipart boundary".to_string())
+        })?;
+        parts.push(Part::new(headers, bytes.slice(body_start..body_start + body_len)));
+        pos = body_start + body_len + close_delimiter.len();
+    }
+}
+
+impl StreamConverter {
+    /// Converts a Hyper body with a `multipart/*` content type into its parts.
+    ///
+    /// Each part exposes its headers and raw content along with the `name` and
+    /// file name from its `Content-Disposition` header (handling quoted values and
+    /// RFC 5987 `filename*` encodings) and its `Content-Type`. Any preamble before
+    /// the first boundary and epilogue after the final one are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    /// * `boundary` - The `boundary` parameter of the body's `Content-Type`
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the parts in order, or a StreamConverterError
+    /// (a `FramingError` if the body is not well-formed multipart)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from(
+    ///         "--XyZ\r\n\
+    ///          Content-Disposition: form-data; name=\"upload\"; filename=\"notes.txt\"\r\n\
+    ///          Content-Type: text/plain\r\n\
+    ///          \r\n\
+    ///          hello\r\n\
+    ///          --XyZ--\r\n",
+    ///     );
+    ///
+    ///     let parts = converter.body_to_multipart(body, "XyZ").await?;
+    ///     assert_eq!(parts[0].filename.as_deref(), Some("notes.txt"));
+    ///     assert_eq!(parts[0].body, "hello");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_multipart(&self, body: Body, boundary: &str) -> Result<Vec<Part>, StreamConverterError> {
+        let bytes = Bytes::from(self.body_to_bytes(body).await?);
+        parse_multipart(bytes, boundary)
+    }
+}
@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use hyper::body::HttpBody;
+use hyper::header::CONTENT_LENGTH;
+use hyper::{Body, Response};
+use tokio::time::Instant;
+
+use crate::error::StreamConverterError;
+use super::StreamConverter;
+
+impl StreamConverter {
+    /// Converts a Hyper body into bytes, reporting the number of bytes received after each chunk.
+    ///
+    /// The callback receives `(bytes_so_far, content_length_hint)`. The hint is the
+    /// body's exact size hint (derived from `Content-Length` for Hyper responses),
+    /// or `None` if the total size is unknown.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    /// * `on_progress` - Called after each chunk with the bytes received so far and the expected total
+    ///
+    /// # Returns
+    ///
+    /// A Result containing either the byte vector or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let bytes = converter
+    ///         .body_to_bytes_with_progress(Body::from("Hello"), |received, total| match total {
+    ///             Some(total) => println!("{}/{} bytes", received, total),
+    ///             None => println!("{} bytes", received),
+    ///         })
+    ///         .await?;
+    ///     assert_eq!(bytes, b"Hello");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_with_progress<F>(
+        &self,
+        body: Body,
+        mut on_progress: F,
+    ) -> Result<Vec<u8>, StreamConverterError>
+    where
+        F: FnMut(usize, Option<usize>),
+    {
+        let total = body
+            .size_hint()
+            .exact()
+            .and_then(|total| usize::try_from(total).ok());
+
+        let mut chunks = self.chunks(body);
+        let mut bytes = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().await {
+            bytes.extend_from_slice(&chunk?);
+            on_progress(bytes.len(), total);
+        }
+        Ok(bytes)
+    }
+
+    /// Converts a Hyper response body into bytes, reporting progress as a percentage.
+    ///
+    /// The total size is read from the response's `Content-Length` header, and the
+    /// callback is invoked after each chunk with a value from 0.0 to 100.0. If the
+    /// header is absent or invalid the total is unknown and the callback is never
+    /// invoked. Percentages are capped at 100.0 if the body exceeds its declared length.
+    ///
+    /// # Arguments
+    ///
+    /// * `resp` - The Hyper response whose body to convert
+    /// * `callback` - Called with the percentage received after each chunk
+    ///
+    /// # Returns
+    ///
+    /// A Result containing either the byte vector or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::{Body, Response};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let resp = Response::builder()
+    ///         .header("Content-Length", "5")
+    ///         .body(Body::from("Hello"))?;
+    ///
+    ///     let bytes = converter
+    ///         .body_to_bytes_with_percent(resp, |percent| println!("{:.1}%", percent))
+    ///         .await?;
+    ///     assert_eq!(bytes, b"Hello");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_with_percent<F>(
+        &self,
+        resp: Response<Body>,
+        mut callback: F,
+    ) -> Result<Vec<u8>, StreamConverterError>
+    where
+        F: FnMut(f64),
+    {
+        let total = resp
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok());
+
+        let mut chunks = self.chunks(resp.into_body());
+        let mut bytes = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = chunk?;
+            bytes.extend_from_slice(&chunk);
+            if let Some(total) = total {
+                let percent = if total == 0 {
+                    100.0
+                } else {
+                    bytes.len() as f64 / total as f64 * 100.0
+                };
+                callback(percent.min(100.0));
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Converts a Hyper body into bytes, measuring the time to first byte and the
+    /// total read time.
+    ///
+    /// Both durations are measured from when this method starts reading. The time
+    /// to first byte ends when the first non-empty chunk arrives; for an empty body
+    /// it equals the total time.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the bytes, the time to first byte and the total read
+    /// time, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let (bytes, ttfb, total) = converter.body_to_bytes_with_ttfb(Body::from("Hello")).await?;
+    ///     println!("{} bytes, first after {:?}, done after {:?}", bytes.len(), ttfb, total);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_with_ttfb(
+        &self,
+        body: Body,
+    ) -> Result<(Vec<u8>, Duration, Duration), StreamConverterError> {
+        let started = Instant::now();
+        let mut first_byte = None;
+
+        let mut chunks = self.chunks(body);
+        let mut bytes = Vec::new();
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = chunk?;
+            if first_byte.is_none() && !chunk.is_empty() {
+                first_byte = Some(started.elapsed());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        let total = started.elapsed();
+        Ok((bytes, first_byte.unwrap_or(total), total))
+    }
+}
@@ -0,0 +1,528 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use bytes::{Buf, Bytes};
+use futures_core::Stream;
+use hyper::body::{HttpBody, SizeHint};
+use hyper::{Body, HeaderMap};
+use tokio::time::Instant;
+
+use crate::error::StreamConverterError;
+use super::convert::{ChunkTimeout, MinRate};
+use super::StreamConverter;
+
+/// Controls how a body's declared length is enforced.
+///
+/// The declared length is the body's exact size hint, which Hyper derives from
+/// the `Content-Length` header. Bodies without a declared length are never checked.
+#[derive(Clone, Default)]
+pub enum LengthCheck {
+    /// Do not compare the received length against the declared length
+    #[default]
+    Off,
+    /// Call the callback with `(expected, received)` on a mismatch, but return the data
+    Warn(Arc<dyn Fn(u64, u64) + Send + Sync>),
+    /// Fail with `UnexpectedEof` or `ExtraData` on a mismatch
+    Strict,
+}
+
+impl LengthCheck {
+    /// Creates a `Warn` check that calls `callback` with `(expected, received)` on a mismatch.
+    pub fn warn<F>(callback: F) -> Self
+    where
+        F: Fn(u64, u64) + Send + Sync + 'static,
+    {
+        LengthCheck::Warn(Arc::new(callback))
+    }
+}
+
+impl fmt::Debug for LengthCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LengthCheck::Off => f.write_str("Off"),
+            LengthCheck::Warn(_) => f.write_str("Warn(..)"),
+            LengthCheck::Strict => f.write_str("Strict"),
+        }
+    }
+}
+
+/// Adapts a stream of byte chunks into an `http_body::Body` so it can be read
+/// by a `ChunkReader`.
+pub(super) struct StreamBody<S> {
+    stream: S,
+    /// Bytes still expected, when the stream's total length is known
+    remaining: Option<u64>,
+}
+
+impl<S> StreamBody<S> {
+    /// Wraps a stream of unknown length.
+    pub(super) fn new(stream: S) -> Self {
+        Self { stream, remaining: None }
+    }
+
+    /// Wraps a stream whose total length, such as a `Content-Length`, is known.
+    ///
+    /// The length is reported as the body's exact size hint, so it is used for
+    /// pre-allocation and checked by a strict `LengthCheck`.
+    #[cfg(feature = "reqwest")]
+    pub(super) fn with_len(stream: S, len: Option<u64>) -> Self {
+        Self { stream, remaining: len }
+    }
+}
+
+impl<S, E> HttpBody for StreamBody<S>
+where
+    S: Stream<Item = Result<Bytes, E>> + Unpin,
+{
+    type Data = Bytes;
+    type Error = E;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let poll = Pin::new(&mut self.stream).poll_next(cx);
+        if let Poll::Ready(Some(Ok(chunk))) = &poll {
+            let len = chunk.len() as u64;
+            if let Some(remaining) = &mut self.remaining {
+                *remaining = remaining.saturating_sub(len);
+            }
+        }
+        poll
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        Poll::Ready(Ok(None))
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match self.remaining {
+            Some(remaining) => SizeHint::with_exact(remaining),
+            None => SizeHint::default(),
+        }
+    }
+}
+
+/// A body or reader that carries a borrow flag for reads through `&mut`.
+///
+/// The `*_borrowed` methods of `StreamConverter` set the flag for the duration
+/// of a read and clear it when the read finishes or is cancelled. In debug
+/// builds, starting a read while the flag is still set panics: that only
+/// happens when an earlier read's future was leaked with `std::mem::forget`,
+/// leaving the body partly consumed, and reading on would silently return
+/// corrupt data. The flag belongs to this wrapper, so unrelated bodies never
+/// affect each other. In release builds the flag is not checked.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use b_stb::converter::GuardedBody;
+/// use b_stb::StreamConverter;
+/// use hyper::Body;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let converter = StreamConverter::new();
+///     let mut body = GuardedBody::new(Body::from("data"));
+///
+///     let bytes = converter.body_to_bytes_borrowed(&mut body).await?;
+///     assert_eq!(bytes, b"data");
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct GuardedBody<T = Body> {
+    inner: T,
+    reading: bool,
+}
+
+impl<T> GuardedBody<T> {
+    /// Wraps a body or reader with a cleared borrow flag.
+    pub fn new(inner: T) -> Self {
+        Self { inner, reading: false }
+    }
+
+    /// Returns a reference to the wrapped body or reader.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Unwraps the body or reader.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Sets the borrow flag for a read, panicking in debug builds if it is already set.
+    pub(super) fn begin_read(&mut self) -> ReadGuard<'_, T> {
+        debug_assert!(
+            !self.reading,
+            "overlapping reads on the same body: an earlier read was leaked while in progress"
+        );
+        self.reading = true;
+        ReadGuard {
+            inner: &mut self.inner,
+            reading: &mut self.reading,
+        }
+    }
+}
+
+/// Access to a `GuardedBody` for one read, clearing its borrow flag when dropped.
+pub(super) struct ReadGuard<'a, T> {
+    pub(super) inner: &'a mut T,
+    reading: &'a mut bool,
+}
+
+impl<T> Drop for ReadGuard<'_, T> {
+    fn drop(&mut self) {
+        *self.reading = false;
+    }
+}
+
+/// Smallest size an adaptive read buffer shrinks to.
+const MIN_ADAPTIVE_BUFFER: usize = 1024;
+
+/// Largest size an adaptive read buffer grows to.
+const MAX_ADAPTIVE_BUFFER: usize = 1024 * 1024;
+
+/// Number of consecutive full (or underfilled) reads before an adaptive buffer is resized.
+const ADAPT_AFTER_READS: u32 = 4;
+
+/// The scratch buffer used when reading from an `AsyncRead`.
+///
+/// With the converter's adaptive buffer enabled, the buffer doubles after
+/// several consecutive reads fill it and halves after several consecutive
+/// reads use a quarter of it or less, staying within fixed bounds.
+pub(super) struct ScratchBuffer {
+    buffer: Vec<u8>,
+    adaptive: bool,
+    min: usize,
+    max: usize,
+    full_reads: u32,
+    short_reads: u32,
+}
+
+impl ScratchBuffer {
+    pub(super) fn new(converter: &StreamConverter) -> Self {
+        let size = converter.buffer_size;
+        Self {
+            buffer: vec![0; size],
+            adaptive: converter.adaptive_buffer,
+            min: size.min(MIN_ADAPTIVE_BUFFER),
+            max: size.max(MAX_ADAPTIVE_BUFFER),
+            full_reads: 0,
+            short_reads: 0,
+        }
+    }
+
+    /// Returns the buffer to read into.
+    pub(super) fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.buffer
+    }
+
+    /// Returns the first `len` bytes of the buffer.
+    pub(super) fn filled(&self, len: usize) -> &[u8] {
+        &self.buffer[..len]
+    }
+
+    /// Records the size of the last read and resizes the buffer if reads have
+    /// consistently filled or underfilled it.
+    pub(super) fn observe(&mut self, bytes_read: usize) {
+        if !self.adaptive {
+            return;
+        }
+        let size = self.buffer.len();
+        if bytes_read == size {
+            self.full_reads += 1;
+            self.short_reads = 0;
+        } else if bytes_read <= size / 4 {
+            self.short_reads += 1;
+            self.full_reads = 0;
+        } else {
+            self.full_reads = 0;
+            self.short_reads = 0;
+        }
+
+        if self.full_reads >= ADAPT_AFTER_READS && size < self.max {
+            self.buffer.resize((size * 2).min(self.max), 0);
+            self.full_reads = 0;
+        } else if self.short_reads >= ADAPT_AFTER_READS && size > self.min {
+            self.buffer.truncate((size / 2).max(self.min));
+            self.buffer.shrink_to_fit();
+            self.short_reads = 0;
+        }
+    }
+}
+
+/// Tracks how many bytes a single conversion has received and applies the
+/// converter's size policies as data arrives.
+pub(super) struct ReadTracker<'a> {
+    converter: &'a StreamConverter,
+    total: usize,
+    soft_limit_crossed: bool,
+    since_yield: usize,
+}
+
+impl<'a> ReadTracker<'a> {
+    pub(super) fn new(converter: &'a StreamConverter) -> Self {
+        Self {
+            converter,
+            total: 0,
+            soft_limit_crossed: false,
+            since_yield: 0,
+        }
+    }
+
+    /// Accounts for `len` newly received bytes.
+    pub(super) fn record(&mut self, len: usize) -> Result<(), StreamConverterError> {
+        let read_so_far = self.total + len;
+        if let Some(limit) = self.converter.max_size {
+            if read_so_far > limit {
+                return Err(StreamConverterError::SizeLimitExceeded { limit, read_so_far });
+            }
+        }
+        self.total = read_so_far;
+        self.since_yield += len;
+
+        if let Some(soft_limit) = &self.converter.soft_limit {
+            if !self.soft_limit_crossed && self.total > soft_limit.limit {
+                self.soft_limit_crossed = true;
+                (soft_limit.callback)(self.total);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Yields to the scheduler if enough bytes have been processed since the last yield.
+    pub(super) async fn maybe_yield(&mut self) {
+        if let Some(every) = self.converter.yield_every {
+            if self.since_yield >= every {
+                self.since_yield %= every;
+                tokio::task::yield_now().await;
+            }
+        }
+    }
+
+    /// Returns the number of bytes recorded so far.
+    pub(super) fn total(&self) -> usize {
+        self.total
+    }
+}
+
+/// Tracks throughput over a sliding window to enforce a minimum rate.
+struct RateWatchdog {
+    min_rate: MinRate,
+    started: Instant,
+    samples: VecDeque<(Instant, usize)>,
+}
+
+impl RateWatchdog {
+    fn new(min_rate: MinRate) -> Self {
+        Self {
+            min_rate,
+            started: Instant::now(),
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Records `len` bytes arriving at `now`.
+    fn record(&mut self, now: Instant, len: usize) {
+        self.samples.push_back((now, len));
+    }
+
+    /// Returns when the rate should next be checked if no data arrives.
+    fn next_check(&self, now: Instant) -> Instant {
+        now + self.min_rate.window / 4
+    }
+
+    /// Fails if the average rate over the most recent window is below the minimum.
+    fn check(&mut self, now: Instant) -> Result<(), StreamConverterError> {
+        let window = self.min_rate.window;
+        if now.duration_since(self.started) < window {
+            return Ok(());
+        }
+        while let Some(&(at, _)) = self.samples.front() {
+            if now.duration_since(at) <= window {
+                break;
+            }
+            self.samples.pop_front();
+        }
+
+        let received: usize = self.samples.iter().map(|&(_, len)| len).sum();
+        let bytes_per_sec = (received as f64 / window.as_secs_f64()) as u64;
+        if bytes_per_sec < self.min_rate.bytes_per_sec {
+            return Err(StreamConverterError::TooSlow {
+                bytes_per_sec,
+                min_bytes_per_sec: self.min_rate.bytes_per_sec,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Counts chunk waits that exceed the per-chunk timeout.
+struct StallCounter {
+    chunk_timeout: ChunkTimeout,
+    stalls: usize,
+}
+
+impl StallCounter {
+    fn new(chunk_timeout: ChunkTimeout) -> Self {
+        Self { chunk_timeout, stalls: 0 }
+    }
+
+    /// Records a stall, failing once more stalls than allowed have occurred.
+    fn record(&mut self) -> Result<(), StreamConverterError> {
+        self.stalls += 1;
+        if self.stalls > self.chunk_timeout.allowed_stalls {
+            return Err(StreamConverterError::Timeout { timeout: self.chunk_timeout.base });
+        }
+        Ok(())
+    }
+}
+
+/// Reads chunks from an `http_body::Body` (a Hyper body by default) on behalf
+/// of a `StreamConverter`.
+///
+/// Every body-consuming method goes through `ChunkReader` so that the
+/// converter's configuration is honoured consistently.
+pub(super) struct ChunkReader<'a, B = Body> {
+    body: B,
+    tracker: ReadTracker<'a>,
+    declared_len: Option<u64>,
+    rate: Option<RateWatchdog>,
+    deadline: Option<Instant>,
+    stalls: Option<StallCounter>,
+    /// The chunk that pushed the body past `max_size`, kept for callers that salvage a prefix
+    overflow: Option<Bytes>,
+}
+
+impl<'a, B> ChunkReader<'a, B>
+where
+    B: HttpBody + Unpin,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    pub(super) fn new(converter: &'a StreamConverter, body: B) -> Self {
+        let declared_len = body.size_hint().exact();
+        Self {
+            body,
+            tracker: ReadTracker::new(converter),
+            declared_len,
+            rate: converter.min_rate.map(RateWatchdog::new),
+            deadline: converter.timeout.map(|timeout| Instant::now() + timeout),
+            stalls: converter.chunk_timeout.map(StallCounter::new),
+            overflow: None,
+        }
+    }
+
+    /// Takes the chunk rejected by the last `SizeLimitExceeded` error, if any.
+    pub(super) fn take_overflow(&mut self) -> Option<Bytes> {
+        self.overflow.take()
+    }
+
+    /// Returns the next chunk of the body, or `None` once the body is exhausted.
+    pub(super) async fn next_chunk(&mut self) -> Option<Result<Bytes, StreamConverterError>> {
+        self.tracker.maybe_yield().await;
+        let next = match self.wait_for_chunk().await {
+            Ok(next) => next,
+            Err(e) => return Some(Err(e)),
+        };
+        let chunk = match next {
+            Some(Ok(mut data)) => data.copy_to_bytes(data.remaining()),
+            Some(Err(e)) => return Some(Err(StreamConverterError::from_body_error(e))),
+            None => return self.check_length_at_end().err().map(Err),
+        };
+        if let Err(e) = self.tracker.record(chunk.len()) {
+            if matches!(e, StreamConverterError::SizeLimitExceeded { .. }) {
+                self.overflow = Some(chunk);
+            }
+            return Some(Err(e));
+        }
+        if let Err(e) = self.check_length_in_progress() {
+            return Some(Err(e));
+        }
+        if let Some(rate) = &mut self.rate {
+            let now = Instant::now();
+            rate.record(now, chunk.len());
+            if let Err(e) = rate.check(now) {
+                return Some(Err(e));
+            }
+        }
+        Some(Ok(chunk))
+    }
+
+    /// Waits for the body's next item, enforcing the overall timeout, the
+    /// per-chunk timeout and the minimum rate while waiting.
+    async fn wait_for_chunk(&mut self) -> Result<Option<Result<B::Data, B::Error>>, StreamConverterError> {
+        let mut stall_at = self
+            .stalls
+            .as_ref()
+            .map(|stalls| Instant::now() + stalls.chunk_timeout.base);
+        loop {
+            let next_check = self.rate.as_ref().map(|rate| rate.next_check(Instant::now()));
+            let wake_at = match [next_check, self.deadline, stall_at].into_iter().flatten().min() {
+                Some(wake_at) => wake_at,
+                None => return Ok(self.body.data().await),
+            };
+            if let Ok(next) = tokio::time::timeout_at(wake_at, self.body.data()).await {
+                return Ok(next);
+            }
+
+            let now = Instant::now();
+            if self.deadline.is_some_and(|deadline| now >= deadline) {
+                let timeout = self.tracker.converter.timeout.unwrap_or_default();
+                return Err(StreamConverterError::Timeout { timeout });
+            }
+            if let (Some(stalls), Some(at)) = (&mut self.stalls, &mut stall_at) {
+                if now >= *at {
+                    stalls.record()?;
+                    *at = now + stalls.chunk_timeout.base;
+                }
+            }
+            if let Some(rate) = &mut self.rate {
+                rate.check(now)?;
+            }
+        }
+    }
+
+    /// Fails early under a strict length check once more than the declared length has arrived.
+    fn check_length_in_progress(&mut self) -> Result<(), StreamConverterError> {
+        let received = self.tracker.total() as u64;
+        match (self.declared_len, &self.tracker.converter.length_check) {
+            (Some(expected), LengthCheck::Strict) if received > expected => {
+                Err(StreamConverterError::ExtraData { expected, received })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Compares the final length against the declared length.
+    fn check_length_at_end(&mut self) -> Result<(), StreamConverterError> {
+        let expected = match self.declared_len.take() {
+            Some(expected) => expected,
+            None => return Ok(()),
+        };
+        let received = self.tracker.total() as u64;
+        if received == expected {
+            return Ok(());
+        }
+        match &self.tracker.converter.length_check {
+            LengthCheck::Off => Ok(()),
+            LengthCheck::Warn(callback) => {
+                callback(expected, received);
+                Ok(())
+            }
+            LengthCheck::Strict if received < expected => {
+                Err(StreamConverterError::UnexpectedEof { expected, received })
+            }
+            LengthCheck::Strict => Err(StreamConverterError::ExtraData { expected, received }),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use reqwest::Response;
+
+use crate::error::StreamConverterError;
+use super::reader::StreamBody;
+use super::StreamConverter;
+
+impl StreamConverter {
+    /// Converts the body of a reqwest response into a String.
+    ///
+    /// The response's byte stream goes through the same collection logic as
+    /// `body_to_string`, so the converter's limits, timeouts and decoding options
+    /// apply. Failures of the stream are reported as `ReqwestError`.
+    ///
+    /// # Arguments
+    ///
+    /// * `resp` - The reqwest response whose body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing either the String or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let resp = reqwest::get("https://example.com").await?;
+    ///
+    ///     let content = converter.response_to_string(resp).await?;
+    ///     println!("{}", content);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn response_to_string(&self, resp: Response) -> Result<String, StreamConverterError> {
+        let len = resp.content_length();
+        self.body_to_string(StreamBody::with_len(Box::pin(resp.bytes_stream()), len)).await
+    }
+
+    /// Converts the body of a reqwest response into a vector of bytes.
+    ///
+    /// The response's byte stream goes through the same collection logic as
+    /// `body_to_bytes`. Failures of the stream are reported as `ReqwestError`.
+    ///
+    /// # Arguments
+    ///
+    /// * `resp` - The reqwest response whose body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing either the byte vector or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let resp = reqwest::get("https://example.com/logo.png").await?;
+    ///
+    ///     let bytes = converter.response_to_bytes(resp).await?;
+    ///     println!("{} bytes", bytes.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn response_to_bytes(&self, resp: Response) -> Result<Vec<u8>, StreamConverterError> {
+        let len = resp.content_length();
+        self.body_to_bytes(StreamBody::with_len(Box::pin(resp.bytes_stream()), len)).await
+    }
+}
@@ -0,0 +1,113 @@
+use std::future::Future;
+use std::io;
+use std::time::Duration;
+
+use hyper::Body;
+
+use crate::error::StreamConverterError;
+use super::StreamConverter;
+
+/// Delay between attempts made by `body_to_bytes_retry`.
+const RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+impl StreamConverterError {
+    /// Returns whether the error may be resolved by fetching the body again.
+    ///
+    /// I/O errors count only when their kind points at the connection, so
+    /// errors such as invalid data or a closed concurrency limit are final.
+    fn is_transient(&self) -> bool {
+        match self {
+            StreamConverterError::HyperError(_) => true,
+            StreamConverterError::IoError(e) => matches!(
+                e.kind(),
+                io::ErrorKind::ConnectionRefused
+                    | io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::NotConnected
+                    | io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::TimedOut
+                    | io::ErrorKind::Interrupted
+                    | io::ErrorKind::UnexpectedEof
+            ),
+            _ => false,
+        }
+    }
+}
+
+impl StreamConverter {
+    /// Converts a Hyper body into bytes, fetching a fresh body and retrying on
+    /// transient errors.
+    ///
+    /// A `Body` can only be read once, so bodies are obtained from `make_body`,
+    /// which is called again for every attempt. `make_body` is fallible, so a
+    /// failure to obtain a body, such as a failed request, counts as an attempt
+    /// just like a failure while reading one. Only `HyperError` and `IoError`s
+    /// of a connection-related kind (such as `ConnectionReset` or `TimedOut`)
+    /// are retried, after a fixed 100ms pause; any other error, such as an
+    /// exceeded size limit or an `InvalidData` I/O error, is returned
+    /// immediately. Once `retries` retries have been used up, the last error is
+    /// returned.
+    ///
+    /// `make_body` previously returned a bare `Body` future. A factory that
+    /// cannot fail now wraps its body in `Ok`, e.g.
+    /// `|| async { Ok::<_, hyper::Error>(Body::from("data")) }`.
+    ///
+    /// # Arguments
+    ///
+    /// * `make_body` - Produces a fresh body for each attempt, or the error that prevented it
+    /// * `retries` - The number of retries after the first attempt
+    ///
+    /// # Returns
+    ///
+    /// A Result containing either the byte vector or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let client = Client::new();
+    ///
+    ///     let bytes = converter
+    ///         .body_to_bytes_retry(
+    ///             || async {
+    ///                 let resp = client.get("http://example.com".parse().unwrap()).await?;
+    ///                 Ok::<_, hyper::Error>(resp.into_body())
+    ///             },
+    ///             3,
+    ///         )
+    ///         .await?;
+    ///     println!("{} bytes", bytes.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_retry<F, Fut, E>(
+        &self,
+        mut make_body: F,
+        retries: usize,
+    ) -> Result<Vec<u8>, StreamConverterError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<Body, E>>,
+        E: Into<StreamConverterError>,
+    {
+        let mut attempt = 0;
+        loop {
+            let result = match make_body().await {
+                Ok(body) => self.body_to_bytes(body).await,
+                Err(e) => Err(e.into()),
+            };
+            match result {
+                Err(e) if e.is_transient() && attempt < retries => {
+                    attempt += 1;
+                    tokio::time::sleep(RETRY_BACKOFF).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use tokio::fs::File;
+use tokio::io::{AsyncBufRead, AsyncSeekExt, BufReader};
+use hyper::Body;
+
+use crate::error::StreamConverterError;
+use super::StreamConverter;
+
+impl StreamConverter {
+    /// Spools a Hyper body to a temporary file and returns a buffered reader over it.
+    ///
+    /// The body is streamed to an anonymous temporary file rather than held in
+    /// memory, so arbitrarily large bodies can be handed to parsers that require
+    /// `AsyncBufRead`. The file is deleted automatically once the reader is dropped.
+    /// The reader's buffer uses the converter's buffer size.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to spool
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a buffered reader positioned at the start of the
+    /// body, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    /// use tokio::io::AsyncBufReadExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let reader = converter.body_to_buf_reader(Body::from("a\nb\n")).await?;
+    ///
+    ///     let mut lines = reader.lines();
+    ///     while let Some(line) = lines.next_line().await? {
+    ///         println!("{}", line);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_buf_reader(
+        &self,
+        body: Body,
+    ) -> Result<impl AsyncBufRead + Unpin, StreamConverterError> {
+        let file = tokio::task::spawn_blocking(tempfile::tempfile)
+            .await
+            .map_err(|e| StreamConverterError::IoError(e.into()))?
+            .map_err(StreamConverterError::IoError)?;
+        let mut file = File::from_std(file);
+
+        self.body_to_writer(body, &mut file).await?;
+        file.rewind().await.map_err(StreamConverterError::IoError)?;
+        Ok(BufReader::with_capacity(self.buffer_size, file))
+    }
+}
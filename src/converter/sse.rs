@@ -0,0 +1,193 @@
+use bytes::{Buf, BytesMut};
+use futures_core::Stream;
+use futures_util::stream;
+use hyper::Body;
+
+use crate::error::StreamConverterError;
+use super::StreamConverter;
+
+/// A single event parsed from a `text/event-stream` body.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SseEvent {
+    /// The `event` field, or `None` for the default `message` type
+    pub event: Option<String>,
+    /// The `data` fields of the event, joined with `\n`
+    pub data: String,
+    /// The `id` field, if present
+    pub id: Option<String>,
+    /// The `retry` field in milliseconds, if present and valid
+    pub retry: Option<u64>,
+}
+
+/// Fields collected for the event currently being parsed.
+#[derive(Default)]
+struct PendingEvent {
+    event: SseEvent,
+    has_data: bool,
+}
+
+impl PendingEvent {
+    /// Applies one `field: value` line to the pending event.
+    fn apply(&mut self, line: &str) {
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event.event = Some(value.to_string()),
+            "data" => {
+                if self.has_data {
+                    self.event.data.push('\n');
+                }
+                self.event.data.push_str(value);
+                self.has_data = true;
+            }
+            "id" if !value.contains('\0') => self.event.id = Some(value.to_string()),
+            "retry" => {
+                if let Ok(retry) = value.parse() {
+                    self.event.retry = Some(retry);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the finished event at a blank line, if it carried any data.
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let pending = std::mem::take(self);
+        pending.has_data.then_some(pending.event)
+    }
+}
+
+/// Splits event-stream data into lines ending in `\r\n`, `\n`, or a lone `\r`.
+#[derive(Default)]
+struct SseLines {
+    buffer: BytesMut,
+    /// Length of the buffered prefix already known to contain no line ending
+    scanned: usize,
+    /// Whether the last line ended in `\r`, so a directly following `\n` belongs to it
+    after_cr: bool,
+}
+
+impl SseLines {
+    /// Appends a chunk of the body.
+    fn push(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Returns the next complete line, without its line ending.
+    fn next_line(&mut self) -> Option<BytesMut> {
+        if self.after_cr {
+            match self.buffer.first() {
+                None => return None,
+                Some(b'\n') => self.buffer.advance(1),
+                Some(_) => {}
+            }
+            self.after_cr = false;
+        }
+        let Some(pos) = self.buffer[self.scanned..].iter().position(|&b| b == b'\n' || b == b'\r') else {
+            self.scanned = self.buffer.len();
+            return None;
+        };
+        let line = self.buffer.split_to(self.scanned + pos);
+        self.after_cr = self.buffer[0] == b'\r';
+        self.buffer.advance(1);
+        self.scanned = 0;
+        Some(line)
+    }
+}
+
+impl StreamConverter {
+    /// Parses a Hyper body as a stream of server-sent events.
+    ///
+    /// This is equivalent to [`StreamConverter::body_sse_events_with_keepalive`]
+    /// with a callback that does nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The `text/event-stream` response body to parse
+    ///
+    /// # Returns
+    ///
+    /// A stream yielding each event, or a StreamConverterError if the body fails
+    pub fn body_sse_events(
+        &self,
+        body: Body,
+    ) -> impl Stream<Item = Result<SseEvent, StreamConverterError>> + '_ {
+        self.body_sse_events_with_keepalive(body, |_| {})
+    }
+
+    /// Parses a Hyper body as a stream of server-sent events, reporting keep-alive comments.
+    ///
+    /// Lines may end in `\r\n`, `\n`, or a lone `\r`, as the event-stream format
+    /// allows, and an event is emitted at each blank line that follows at least
+    /// one `data` field. Lines starting with `:` are comments, which servers send
+    /// as keep-alives; they never produce events, but `keepalive` is called with
+    /// the comment text (without the leading `:`) so callers can reset a watchdog
+    /// timer. An event left unterminated at the end of the body is discarded,
+    /// regardless of the converter's [`TrailingNewline`](super::TrailingNewline)
+    /// setting, which applies only to `body_lines`.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The `text/event-stream` response body to parse
+    /// * `keepalive` - Called with the text of each comment line
+    ///
+    /// # Returns
+    ///
+    /// A stream yielding each event, or a StreamConverterError if the body fails
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use futures_util::StreamExt;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from(": ping\n\nevent: update\ndata: 42\n\n");
+    ///
+    ///     let mut events = Box::pin(converter.body_sse_events_with_keepalive(body, |_| {
+    ///         println!("keep-alive received");
+    ///     }));
+    ///     while let Some(event) = events.next().await {
+    ///         let event = event?;
+    ///         println!("{:?}: {}", event.event, event.data);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn body_sse_events_with_keepalive<'a, F>(
+        &'a self,
+        body: Body,
+        keepalive: F,
+    ) -> impl Stream<Item = Result<SseEvent, StreamConverterError>> + 'a
+    where
+        F: FnMut(&str) + 'a,
+    {
+        let state = (self.chunks(body), SseLines::default(), keepalive, PendingEvent::default());
+        stream::try_unfold(state, move |(mut chunks, mut lines, mut keepalive, mut pending)| async move {
+            loop {
+                while let Some(line) = lines.next_line() {
+                    let line = self.decode_utf8(line.to_vec())?;
+                    if line.is_empty() {
+                        if let Some(event) = pending.dispatch() {
+                            return Ok(Some((event, (chunks, lines, keepalive, pending))));
+                        }
+                    } else if let Some(comment) = line.strip_prefix(':') {
+                        keepalive(comment);
+                    } else {
+                        pending.apply(&line);
+                    }
+                }
+                match chunks.next_chunk().await {
+                    Some(chunk) => lines.push(&chunk?),
+                    // Any unterminated line belongs to an event that is discarded
+                    None => return Ok(None),
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,97 @@
+use bytes::Bytes;
+use futures_core::Stream;
+use futures_util::stream;
+use hyper::{Body, HeaderMap, Response, StatusCode};
+
+use crate::error::StreamConverterError;
+use super::StreamConverter;
+
+/// A response whose status and headers are available before its body has arrived.
+///
+/// Created by [`StreamConverter::streaming_response`]. The status and headers
+/// can be inspected (or forwarded, as a proxy would) immediately, and the body
+/// is then consumed lazily with [`StreamingResponse::body_stream`], under the
+/// converter's configuration.
+#[derive(Debug)]
+pub struct StreamingResponse<'a> {
+    converter: &'a StreamConverter,
+    response: Response<Body>,
+}
+
+impl<'a> StreamingResponse<'a> {
+    /// Returns the response status.
+    pub fn status(&self) -> StatusCode {
+        self.response.status()
+    }
+
+    /// Returns the response headers.
+    pub fn headers(&self) -> &HeaderMap {
+        self.response.headers()
+    }
+
+    /// Consumes the response and streams its body chunk by chunk.
+    ///
+    /// Chunks are read only as the stream is polled, and errors are reported as
+    /// `StreamConverterError`.
+    pub fn body_stream(self) -> impl Stream<Item = Result<Bytes, StreamConverterError>> + 'a {
+        let chunks = self.converter.chunks(self.response.into_body());
+        stream::try_unfold(chunks, |mut chunks| async move {
+            match chunks.next_chunk().await {
+                Some(chunk) => Ok(Some((chunk?, chunks))),
+                None => Ok(None),
+            }
+        })
+    }
+
+    /// Consumes the wrapper and returns the underlying response.
+    pub fn into_inner(self) -> Response<Body> {
+        self.response
+    }
+}
+
+impl StreamConverter {
+    /// Wraps a response so its headers can be used before its body is consumed.
+    ///
+    /// Unlike the collecting methods, nothing is read from the body until
+    /// [`StreamingResponse::body_stream`] is polled. This lets a proxy forward the
+    /// status and headers while the body is still arriving.
+    ///
+    /// # Arguments
+    ///
+    /// * `resp` - The Hyper response to wrap
+    ///
+    /// # Returns
+    ///
+    /// A `StreamingResponse` exposing the status, headers, and a lazy body stream
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use futures_util::StreamExt;
+    /// use hyper::{Body, Response};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let resp = Response::builder()
+    ///         .header("Content-Type", "text/plain")
+    ///         .body(Body::from("streamed"))?;
+    ///
+    ///     let streaming = converter.streaming_response(resp);
+    ///     println!("{} {:?}", streaming.status(), streaming.headers());
+    ///
+    ///     let mut chunks = Box::pin(streaming.body_stream());
+    ///     while let Some(chunk) = chunks.next().await {
+    ///         println!("{:?}", chunk?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn streaming_response(&self, resp: Response<Body>) -> StreamingResponse<'_> {
+        StreamingResponse {
+            converter: self,
+            response: resp,
+        }
+    }
+}
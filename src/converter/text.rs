@@ -0,0 +1,761 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::string::FromUtf8Error;
+
+use bytes::BytesMut;
+use futures_core::Stream;
+use futures_util::{stream, StreamExt, TryStreamExt};
+use hyper::body::HttpBody;
+use hyper::Body;
+
+use crate::error::StreamConverterError;
+use crate::util::parse_header_block;
+use super::reader::ChunkReader;
+use super::StreamConverter;
+
+/// Controls whether a line stream emits an empty final line when the body
+/// ends with a newline.
+///
+/// With `Ignore`, `"a\nb\n"` yields `["a", "b"]`; with `EmitEmpty` it yields
+/// `["a", "b", ""]`. Bodies without a trailing newline are unaffected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrailingNewline {
+    /// Do not emit a line after a trailing newline
+    #[default]
+    Ignore,
+    /// Emit an empty line after a trailing newline
+    EmitEmpty,
+}
+
+/// Controls how `body_to_string` handles embedded NUL (`\0`) bytes.
+///
+/// This is useful when bridging to systems, such as C APIs, that treat NUL
+/// as a string terminator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NulPolicy {
+    /// Keep NUL bytes in the output
+    #[default]
+    Keep,
+    /// Remove NUL bytes from the output
+    Strip,
+    /// Fail with `StreamConverterError::NulByte` at the first NUL byte
+    Error,
+}
+
+impl NulPolicy {
+    /// Applies the policy to collected body bytes.
+    pub(super) fn apply(self, bytes: &mut Vec<u8>) -> Result<(), StreamConverterError> {
+        match self {
+            NulPolicy::Keep => {}
+            NulPolicy::Strip => bytes.retain(|&b| b != 0),
+            NulPolicy::Error => {
+                if let Some(position) = bytes.iter().position(|&b| b == 0) {
+                    return Err(StreamConverterError::NulByte { position });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The predominant line-ending style detected in a body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// Most line endings are `\n`, or the body has no line endings at all
+    Lf,
+    /// Most line endings are `\r\n`
+    CrLf,
+    /// `\n` and `\r\n` line endings are equally common
+    Mixed,
+}
+
+/// Incrementally decodes UTF-8 whose multibyte sequences may span chunk boundaries.
+pub(super) struct Utf8Decoder {
+    pending: Vec<u8>,
+    lossy: bool,
+}
+
+impl Utf8Decoder {
+    pub(super) fn new(lossy: bool) -> Self {
+        Self {
+            pending: Vec::new(),
+            lossy,
+        }
+    }
+
+    /// Decodes as much of the buffered input plus `input` as possible into `out`.
+    ///
+    /// An incomplete sequence at the end is held back until more input arrives.
+    pub(super) fn decode(&mut self, input: &[u8], out: &mut String) -> Result<(), FromUtf8Error> {
+        self.pending.extend_from_slice(input);
+        let mut start = 0;
+        while start < self.pending.len() {
+            let error = match std::str::from_utf8(&self.pending[start..]) {
+                Ok(text) => {
+                    out.push_str(text);
+                    start = self.pending.len();
+                    break;
+                }
+                Err(error) => error,
+            };
+
+            let valid_end = start + error.valid_up_to();
+            out.push_str(std::str::from_utf8(&self.pending[start..valid_end]).expect("prefix is valid UTF-8"));
+            start = valid_end;
+            match error.error_len() {
+                None => break,
+                Some(len) if self.lossy => {
+                    out.push(char::REPLACEMENT_CHARACTER);
+                    start += len;
+                }
+                Some(_) => return Err(invalid_utf8(self.pending.split_off(start))),
+            }
+        }
+        self.pending.drain(..start);
+        Ok(())
+    }
+
+    /// Flushes any incomplete sequence left at the end of the input.
+    pub(super) fn finish(&mut self, out: &mut String) -> Result<(), FromUtf8Error> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        if self.lossy {
+            self.pending.clear();
+            out.push(char::REPLACEMENT_CHARACTER);
+            return Ok(());
+        }
+        Err(invalid_utf8(std::mem::take(&mut self.pending)))
+    }
+}
+
+/// Builds the UTF-8 error for bytes known to be invalid.
+fn invalid_utf8(bytes: Vec<u8>) -> FromUtf8Error {
+    String::from_utf8(bytes).expect_err("bytes are invalid UTF-8")
+}
+
+/// State carried between items of a character stream.
+struct CharState<'a> {
+    chunks: ChunkReader<'a>,
+    decoder: Utf8Decoder,
+    decoded: VecDeque<char>,
+    finished: bool,
+}
+
+/// State carried between items of a line stream.
+struct LineState<'a, B> {
+    chunks: ChunkReader<'a, B>,
+    buffer: BytesMut,
+    /// Length of the buffered prefix already known to contain no newline
+    scanned: usize,
+    ends_with_newline: bool,
+    finished: bool,
+}
+
+/// Parses a non-blank, non-comment line of an environment file into its key
+/// and value.
+///
+/// Returns `None` if the line is malformed.
+fn parse_env_line(line: &str) -> Option<(String, String)> {
+    let line = line.strip_prefix("export ").map_or(line, str::trim_start);
+    let (key, value) = line.split_once('=')?;
+    let key = key.trim_end();
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.') {
+        return None;
+    }
+
+    let value = value.trim_start();
+    let (value, rest) = if let Some(quoted) = value.strip_prefix('\'') {
+        let end = quoted.find('\'')?;
+        (quoted[..end].to_string(), &quoted[end + 1..])
+    } else if let Some(quoted) = value.strip_prefix('"') {
+        unquote_env_value(quoted)?
+    } else {
+        // An unquoted value ends at a `#` preceded by whitespace
+        let end = value
+            .char_indices()
+            .find(|&(i, c)| c == '#' && value[..i].ends_with([' ', '\t']))
+            .map_or(value.len(), |(i, _)| i);
+        (value[..end].trim_end().to_string(), "")
+    };
+
+    let rest = rest.trim_start();
+    if !rest.is_empty() && !rest.starts_with('#') {
+        return None;
+    }
+    Some((key.to_string(), value))
+}
+
+/// Reads a double-quoted value (after its opening quote), resolving `\n`,
+/// `\r`, `\t`, `\"` and `\\` escapes.
+///
+/// Returns the unquoted value and the input following the closing quote, or
+/// `None` if the closing quote is missing.
+fn unquote_env_value(quoted: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = quoted.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                escaped @ ('"' | '\\') => value.push(escaped),
+                other => {
+                    value.push('\\');
+                    value.push(other);
+                }
+            },
+            '"' => return Some((value, &quoted[i + 1..])),
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+impl StreamConverter {
+    /// Converts a Hyper body into a String and counts its lines in the same pass.
+    ///
+    /// Lines are delimited by `\n`. A final line without a trailing newline is
+    /// counted, and an empty body has zero lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the converted String and its line count, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let (content, lines) = converter
+    ///         .body_to_string_with_line_count(Body::from("a\nb\nc"))
+    ///         .await?;
+    ///     assert_eq!(content, "a\nb\nc");
+    ///     assert_eq!(lines, 3);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_string_with_line_count(
+        &self,
+        body: Body,
+    ) -> Result<(String, usize), StreamConverterError> {
+        let mut chunks = self.chunks(body);
+        let mut bytes = Vec::new();
+        let mut newlines = 0;
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = chunk?;
+            newlines += chunk.iter().filter(|&&b| b == b'\n').count();
+            bytes.extend_from_slice(&chunk);
+        }
+
+        let lines = match bytes.last() {
+            Some(b'\n') | None => newlines,
+            Some(_) => newlines + 1,
+        };
+        self.apply_strip_bom(&mut bytes);
+        self.nul_policy.apply(&mut bytes)?;
+        let content = self.decode_utf8(bytes)?;
+        Ok((content, lines))
+    }
+
+    /// Streams a Hyper body as lines of text.
+    ///
+    /// Incoming chunks are buffered and split on `\n` (a preceding `\r` is also
+    /// removed), yielding each complete line as soon as it arrives. A partial
+    /// line is held back until more data arrives, and any remaining content at
+    /// the end of the body is emitted as a final line. Each byte is scanned for
+    /// a newline only once, so a long line arriving in many small chunks costs
+    /// time linear in its length. Whether an empty line is
+    /// emitted after a trailing newline is controlled by
+    /// [`StreamConverter::with_trailing_newline`].
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body (or other `http_body::Body`) to split into lines
+    ///
+    /// # Returns
+    ///
+    /// A stream yielding each line, or a StreamConverterError if the body fails
+    /// or a line is not valid UTF-8
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use futures_util::StreamExt;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let mut lines = Box::pin(converter.body_lines(Body::from("a\nb\n")));
+    ///
+    ///     while let Some(line) = lines.next().await {
+    ///         println!("line: {}", line?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn body_lines<'a, B>(&'a self, body: B) -> impl Stream<Item = Result<String, StreamConverterError>> + 'a
+    where
+        B: HttpBody + Unpin + 'a,
+        B::Error: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let state = LineState {
+            chunks: self.chunks(body),
+            buffer: BytesMut::new(),
+            scanned: 0,
+            ends_with_newline: false,
+            finished: false,
+        };
+        stream::try_unfold(state, move |mut state| async move {
+            loop {
+                if let Some(pos) = state.buffer[state.scanned..].iter().position(|&b| b == b'\n') {
+                    let mut line = state.buffer.split_to(state.scanned + pos + 1);
+                    state.scanned = 0;
+                    line.truncate(line.len() - 1);
+                    if line.last() == Some(&b'\r') {
+                        line.truncate(line.len() - 1);
+                    }
+                    return Ok(Some((self.decode_utf8(line.to_vec())?, state)));
+                }
+                state.scanned = state.buffer.len();
+
+                if state.finished {
+                    if !state.buffer.is_empty() {
+                        let line = state.buffer.split().to_vec();
+                        state.scanned = 0;
+                        return Ok(Some((self.decode_utf8(line)?, state)));
+                    }
+                    if state.ends_with_newline && self.trailing_newline == TrailingNewline::EmitEmpty {
+                        state.ends_with_newline = false;
+                        return Ok(Some((String::new(), state)));
+                    }
+                    return Ok(None);
+                }
+
+                match state.chunks.next_chunk().await {
+                    Some(chunk) => {
+                        let chunk = chunk?;
+                        if let Some(&last) = chunk.last() {
+                            state.ends_with_newline = last == b'\n';
+                        }
+                        state.buffer.extend_from_slice(&chunk);
+                    }
+                    None => state.finished = true,
+                }
+            }
+        })
+    }
+
+    /// Converts a Hyper body into a vector of its lines.
+    ///
+    /// This is the eager counterpart of [`StreamConverter::body_lines`]. The body
+    /// is collected and split on `\n` or `\r\n`; a trailing newline does not
+    /// produce an empty final line.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to split into lines
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the lines, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let lines = converter.body_to_lines(Body::from("a\r\nb\n")).await?;
+    ///     assert_eq!(lines, vec!["a", "b"]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_lines(&self, body: Body) -> Result<Vec<String>, StreamConverterError> {
+        let content = self.body_to_string(body).await?;
+        Ok(content.lines().map(String::from).collect())
+    }
+
+    /// Converts a Hyper body into at most `max_lines` lines, without reading further.
+    ///
+    /// Lines are produced as by `body_lines`. Once `max_lines` lines have been
+    /// collected, the rest of the body is dropped unread, which bounds memory and
+    /// processing for potentially huge line-oriented responses such as log tails.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    /// * `max_lines` - The maximum number of lines to collect
+    ///
+    /// # Returns
+    ///
+    /// A Result containing up to `max_lines` lines, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let lines = converter.body_to_lines_limited(Body::from("a\nb\nc\n"), 2).await?;
+    ///     assert_eq!(lines, vec!["a", "b"]);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_lines_limited(
+        &self,
+        body: Body,
+        max_lines: usize,
+    ) -> Result<Vec<String>, StreamConverterError> {
+        self.body_lines(body).take(max_lines).try_collect().await
+    }
+
+    /// Converts a Hyper body into a String, replacing at most `max` invalid UTF-8 sequences.
+    ///
+    /// Like `body_to_string_lossy`, each invalid sequence becomes U+FFFD
+    /// REPLACEMENT CHARACTER, but the conversion fails once more than `max`
+    /// replacements are needed. This sits between strict decoding (`max` of 0)
+    /// and fully lossy decoding.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    /// * `max` - The maximum number of replacements to allow
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the converted String, or a `TooManyReplacements`
+    /// error if the text is more corrupted than allowed
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from(vec![b'o', b'k', 0xFF]);
+    ///
+    ///     let content = converter.body_to_string_lossy_max_replacements(body, 3).await?;
+    ///     assert_eq!(content, "ok\u{FFFD}");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_string_lossy_max_replacements(
+        &self,
+        body: Body,
+        max: usize,
+    ) -> Result<String, StreamConverterError> {
+        let mut bytes = self.body_to_bytes(body).await?;
+        self.apply_strip_bom(&mut bytes);
+        self.nul_policy.apply(&mut bytes)?;
+        let mut content = String::with_capacity(bytes.len());
+        let mut replacements = 0;
+        for chunk in bytes.utf8_chunks() {
+            content.push_str(chunk.valid());
+            if !chunk.invalid().is_empty() {
+                replacements += 1;
+                if replacements > max {
+                    return Err(StreamConverterError::TooManyReplacements { max });
+                }
+                content.push(char::REPLACEMENT_CHARACTER);
+            }
+        }
+        Ok(content)
+    }
+
+    /// Converts a Hyper body into a String containing only printable characters.
+    ///
+    /// The body is decoded as UTF-8 and then rejected if it contains any control
+    /// character other than tab (`\t`) or newline (`\n`). Carriage returns,
+    /// escape sequences and other C0/C1 controls are refused, which guards text
+    /// destined for logs or terminals against control-character injection.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the converted String, or a `NonPrintable` error
+    /// identifying the first offending character
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let content = converter.body_to_printable_string(Body::from("user=alice\n")).await?;
+    ///     assert_eq!(content, "user=alice\n");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_printable_string(&self, body: Body) -> Result<String, StreamConverterError> {
+        let content = self.body_to_string(body).await?;
+        if let Some((position, codepoint)) = content
+            .char_indices()
+            .find(|&(_, c)| c.is_control() && c != '\t' && c != '\n')
+        {
+            return Err(StreamConverterError::NonPrintable { codepoint, position });
+        }
+        Ok(content)
+    }
+
+    /// Converts a Hyper body into a normalized String for fuzzy comparisons.
+    ///
+    /// The body is decoded as by `body_to_string`, then every run of whitespace
+    /// is collapsed to a single space, leading and trailing whitespace is
+    /// removed, and the text is lowercased. This makes golden-file assertions
+    /// robust against trivial formatting differences; use `body_to_string` when
+    /// the exact text matters.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the normalized String, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from("  Hello,\r\n\tWORLD!  ");
+    ///
+    ///     let normalized = converter.body_to_normalized_string(body).await?;
+    ///     assert_eq!(normalized, "hello, world!");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_normalized_string(&self, body: Body) -> Result<String, StreamConverterError> {
+        let content = self.body_to_string(body).await?;
+        Ok(content.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase())
+    }
+
+    /// Converts a Hyper body holding a `.env`-style file into a map of variables.
+    ///
+    /// Each line has the form `KEY=VALUE`, optionally preceded by `export`.
+    /// Blank lines and lines starting with `#` are skipped. Values may be wrapped
+    /// in single quotes (taken literally) or double quotes (supporting `\n`,
+    /// `\r`, `\t`, `\"` and `\\` escapes); unquoted values end at a `#` that
+    /// follows whitespace and are trimmed. When a key appears more than once,
+    /// the last value wins.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the variables by name, or a StreamConverterError
+    /// (an `EnvSyntaxError` carrying the 1-based line number and content of the
+    /// first malformed line)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let body = Body::from("# settings\nHOST=localhost\nGREETING=\"hello world\"\n");
+    ///
+    ///     let vars = converter.body_to_env_map(body).await?;
+    ///     assert_eq!(vars["HOST"], "localhost");
+    ///     assert_eq!(vars["GREETING"], "hello world");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_env_map(&self, body: Body) -> Result<HashMap<String, String>, StreamConverterError> {
+        let content = self.body_to_string(body).await?;
+        let mut vars = HashMap::new();
+        for (index, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let (key, value) = parse_env_line(trimmed).ok_or_else(|| StreamConverterError::EnvSyntaxError {
+                line: index + 1,
+                content: line.to_string(),
+            })?;
+            vars.insert(key, value);
+        }
+        Ok(vars)
+    }
+
+    /// Converts a Hyper body containing a raw HTTP header block into name/value pairs.
+    ///
+    /// The body is collected in full and then parsed with
+    /// [`parse_header_block`](crate::util::parse_header_block), preserving
+    /// header order and duplicates.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper body containing the header block
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the parsed headers, or a StreamConverterError
+    pub async fn body_to_headers(&self, body: Body) -> Result<Vec<(String, String)>, StreamConverterError> {
+        let bytes = self.body_to_bytes(body).await?;
+        parse_header_block(&bytes)
+    }
+
+    /// Converts a Hyper body into a String and reports its predominant line-ending style.
+    ///
+    /// Line endings are counted while the body is collected, including `\r\n`
+    /// pairs split across chunks. The more common style wins, so a stray `\n` in
+    /// a `\r\n` file still reports `CrLf`; `Mixed` is reported only when both
+    /// styles are equally common. A body without line endings reports `Lf`.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the converted String and its [`LineEnding`], or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use b_stb::converter::LineEnding;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let (_, eol) = converter.body_to_string_with_eol(Body::from("a\r\nb\r\n")).await?;
+    ///     assert_eq!(eol, LineEnding::CrLf);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_string_with_eol(&self, body: Body) -> Result<(String, LineEnding), StreamConverterError> {
+        let mut chunks = self.chunks(body);
+        let mut bytes = Vec::new();
+        let (mut lf, mut crlf) = (0usize, 0usize);
+        while let Some(chunk) = chunks.next_chunk().await {
+            let chunk = chunk?;
+            let mut prev = bytes.last().copied();
+            for &b in chunk.iter() {
+                if b == b'\n' {
+                    if prev == Some(b'\r') {
+                        crlf += 1;
+                    } else {
+                        lf += 1;
+                    }
+                }
+                prev = Some(b);
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        let eol = match lf.cmp(&crlf) {
+            Ordering::Greater => LineEnding::Lf,
+            Ordering::Less => LineEnding::CrLf,
+            Ordering::Equal if lf == 0 => LineEnding::Lf,
+            Ordering::Equal => LineEnding::Mixed,
+        };
+        self.apply_strip_bom(&mut bytes);
+        self.nul_policy.apply(&mut bytes)?;
+        Ok((self.decode_utf8(bytes)?, eol))
+    }
+
+    /// Streams a Hyper body as individual characters.
+    ///
+    /// UTF-8 is decoded incrementally, buffering multibyte sequences that span
+    /// chunk boundaries. Invalid sequences produce an `EncodingError`, or U+FFFD
+    /// REPLACEMENT CHARACTER if the converter is lossy (see [`StreamConverter::with_lossy`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to decode
+    ///
+    /// # Returns
+    ///
+    /// A stream yielding each character, or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use futures_util::StreamExt;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let mut chars = Box::pin(converter.body_chars(Body::from("héllo")));
+    ///
+    ///     while let Some(c) = chars.next().await {
+    ///         print!("{}", c?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn body_chars(
+        &self,
+        body: Body,
+    ) -> impl Stream<Item = Result<char, StreamConverterError>> + '_ {
+        let state = CharState {
+            chunks: self.chunks(body),
+            decoder: Utf8Decoder::new(self.lossy),
+            decoded: VecDeque::new(),
+            finished: false,
+        };
+        stream::try_unfold(state, move |mut state| async move {
+            loop {
+                if let Some(c) = state.decoded.pop_front() {
+                    return Ok(Some((c, state)));
+                }
+                if state.finished {
+                    return Ok(None);
+                }
+
+                let mut text = String::new();
+                match state.chunks.next_chunk().await {
+                    Some(chunk) => state.decoder.decode(&chunk?, &mut text),
+                    None => {
+                        state.finished = true;
+                        state.decoder.finish(&mut text)
+                    }
+                }
+                .map_err(|e| self.encoding_error(e))?;
+                state.decoded.extend(text.chars());
+            }
+        })
+    }
+}
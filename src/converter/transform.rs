@@ -0,0 +1,279 @@
+use std::error::Error;
+use std::future::Future;
+
+use bytes::Bytes;
+use futures_core::Stream;
+use futures_util::stream;
+use hyper::Body;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+use crate::error::StreamConverterError;
+use super::StreamConverter;
+
+/// A pluggable, stateful transformation applied to each chunk of a body.
+///
+/// Implementations can perform decompression, decryption, re-framing, or any
+/// other chunk-by-chunk processing. `transform` is called for each incoming
+/// chunk, and `finish` is called once at the end of the body to flush any
+/// trailing state. Errors are reported as `StreamConverterError::TransformError`.
+///
+/// # Examples
+///
+/// ```rust
+/// use b_stb::converter::ChunkTransform;
+/// use bytes::Bytes;
+/// use std::convert::Infallible;
+///
+/// struct Uppercase;
+///
+/// impl ChunkTransform for Uppercase {
+///     type Error = Infallible;
+///
+///     async fn transform(&mut self, chunk: Bytes) -> Result<Bytes, Self::Error> {
+///         Ok(Bytes::from(chunk.to_ascii_uppercase()))
+///     }
+/// }
+/// ```
+pub trait ChunkTransform {
+    /// The error produced when a chunk cannot be transformed
+    type Error: Into<Box<dyn Error + Send + Sync>>;
+
+    /// Transforms a single chunk of the body.
+    fn transform(&mut self, chunk: Bytes) -> impl Future<Output = Result<Bytes, Self::Error>> + Send;
+
+    /// Flushes any trailing state once the body has ended.
+    ///
+    /// The default implementation produces no further output.
+    fn finish(&mut self) -> impl Future<Output = Result<Bytes, Self::Error>> + Send {
+        async { Ok(Bytes::new()) }
+    }
+}
+
+/// Wraps an error returned by a `ChunkTransform`.
+pub(super) fn transform_error<E>(error: E) -> StreamConverterError
+where
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    StreamConverterError::TransformError(error.into())
+}
+
+impl StreamConverter {
+    /// Streams a Hyper body through a user-supplied [`ChunkTransform`].
+    ///
+    /// Each chunk is passed to the transform as it arrives, and the output of
+    /// `finish` is emitted once the body ends. Empty outputs are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to transform
+    /// * `transform` - The transform to apply to each chunk
+    ///
+    /// # Returns
+    ///
+    /// A stream yielding the transformed chunks, or a StreamConverterError if
+    /// the body or the transform fails
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use b_stb::converter::ChunkTransform;
+    /// use bytes::Bytes;
+    /// use futures_util::StreamExt;
+    /// use hyper::Body;
+    /// use std::convert::Infallible;
+    ///
+    /// struct Uppercase;
+    ///
+    /// impl ChunkTransform for Uppercase {
+    ///     type Error = Infallible;
+    ///
+    ///     async fn transform(&mut self, chunk: Bytes) -> Result<Bytes, Self::Error> {
+    ///         Ok(Bytes::from(chunk.to_ascii_uppercase()))
+    ///     }
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let mut chunks = Box::pin(converter.body_transform(Body::from("hello"), Uppercase));
+    ///
+    ///     while let Some(chunk) = chunks.next().await {
+    ///         println!("{:?}", chunk?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn body_transform<'a, T>(
+        &'a self,
+        body: Body,
+        transform: T,
+    ) -> impl Stream<Item = Result<Bytes, StreamConverterError>> + 'a
+    where
+        T: ChunkTransform + 'a,
+    {
+        let state = (self.chunks(body), transform, false);
+        stream::try_unfold(state, |(mut chunks, mut transform, mut finished)| async move {
+            while !finished {
+                let output = match chunks.next_chunk().await {
+                    Some(chunk) => transform.transform(chunk?).await,
+                    None => {
+                        finished = true;
+                        transform.finish().await
+                    }
+                };
+                let output = output.map_err(transform_error)?;
+                if !output.is_empty() {
+                    return Ok(Some((output, (chunks, transform, finished))));
+                }
+            }
+            Ok(None)
+        })
+    }
+
+    /// Streams a Hyper body through a [`ChunkTransform`] and writes the output to an `AsyncWrite`.
+    ///
+    /// Each chunk is transformed and written as it arrives, so the transformed
+    /// body (for example, a decompressed download) is never buffered in full.
+    /// Reading runs ahead of the writer by at most the converter's forward
+    /// buffer (see [`StreamConverter::with_forward_buffer`]).
+    /// The writer is flushed once the body and the transform's `finish` output
+    /// have been written.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to transform
+    /// * `writer` - The writer that receives the transformed bytes
+    /// * `transform` - The transform to apply to each chunk
+    ///
+    /// # Returns
+    ///
+    /// A Result containing the number of bytes written, a `TransformError` if the
+    /// transform fails, or an `IoError` if the writer fails
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use b_stb::converter::ChunkTransform;
+    /// use bytes::Bytes;
+    /// use hyper::Body;
+    /// use std::convert::Infallible;
+    ///
+    /// struct Uppercase;
+    ///
+    /// impl ChunkTransform for Uppercase {
+    ///     type Error = Infallible;
+    ///
+    ///     async fn transform(&mut self, chunk: Bytes) -> Result<Bytes, Self::Error> {
+    ///         Ok(Bytes::from(chunk.to_ascii_uppercase()))
+    ///     }
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let mut file = tokio::fs::File::create("out.txt").await?;
+    ///
+    ///     let written = converter
+    ///         .body_to_writer_transform(Body::from("hello"), &mut file, Uppercase)
+    ///         .await?;
+    ///     println!("Wrote {} bytes", written);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_writer_transform<W, T>(
+        &self,
+        body: Body,
+        writer: &mut W,
+        mut transform: T,
+    ) -> Result<u64, StreamConverterError>
+    where
+        W: AsyncWrite + Unpin,
+        T: ChunkTransform,
+    {
+        let (producer, chunks) = self.forward(body);
+        let consumer = async {
+            // Owning the receiver drops it on error, which stops the producer
+            let mut chunks = chunks;
+            let mut written = 0u64;
+            loop {
+                let (output, finished) = match chunks.recv().await {
+                    Some(chunk) => (transform.transform(chunk?).await, false),
+                    None => (transform.finish().await, true),
+                };
+                let output = output.map_err(transform_error)?;
+                writer.write_all(&output).await.map_err(StreamConverterError::IoError)?;
+                written += output.len() as u64;
+                if finished {
+                    writer.flush().await.map_err(StreamConverterError::IoError)?;
+                    return Ok(written);
+                }
+            }
+        };
+        let ((), written) = futures_util::future::join(producer, consumer).await;
+        written
+    }
+
+    /// Converts a Hyper body into bytes, applying `f` in place to the first
+    /// `prefix_len` bytes only.
+    ///
+    /// This suits formats with a transformed header followed by a plain body. The
+    /// transform is deferred until `prefix_len` bytes have been collected, so a
+    /// prefix spanning several chunks is passed to `f` as one contiguous slice.
+    /// `f` is called exactly once; if the body is shorter than `prefix_len`, it
+    /// receives the whole body.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The Hyper response body to convert
+    /// * `prefix_len` - The number of leading bytes to transform
+    /// * `f` - Transforms the prefix in place
+    ///
+    /// # Returns
+    ///
+    /// A Result containing either the byte vector or a StreamConverterError
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use b_stb::StreamConverter;
+    /// use hyper::Body;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let converter = StreamConverter::new();
+    ///     let bytes = converter
+    ///         .body_to_bytes_transform_prefix(Body::from("head:body"), 4, |prefix| {
+    ///             prefix.make_ascii_uppercase()
+    ///         })
+    ///         .await?;
+    ///     assert_eq!(bytes, b"HEAD:body");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn body_to_bytes_transform_prefix<F>(
+        &self,
+        body: Body,
+        prefix_len: usize,
+        mut f: F,
+    ) -> Result<Vec<u8>, StreamConverterError>
+    where
+        F: FnMut(&mut [u8]),
+    {
+        let mut chunks = self.chunks(body);
+        let mut bytes = Vec::new();
+        let mut transformed = false;
+        while let Some(chunk) = chunks.next_chunk().await {
+            bytes.extend_from_slice(&chunk?);
+            if !transformed && bytes.len() >= prefix_len {
+                f(&mut bytes[..prefix_len]);
+                transformed = true;
+            }
+        }
+        if !transformed {
+            f(&mut bytes);
+        }
+        Ok(bytes)
+    }
+}
@@ -2,6 +2,7 @@ use std::error::Error;
 use std::fmt;
 use std::string::FromUtf8Error;
 use std::io;
+use std::time::Duration;
 use hyper::Error as HyperError;
 
 /// Represents all possible errors that can occur when converting streams.
@@ -10,6 +11,31 @@ use hyper::Error as HyperError;
 /// - I/O operations
 /// - UTF-8 encoding issues
 /// - Hyper-specific errors
+/// - Errors from other `http_body::Body` implementations
+/// - CBOR deserialization errors (with the `cbor` feature)
+/// - JSON deserialization errors (with the `json` feature)
+/// - reqwest errors (with the `reqwest` feature)
+/// - Text with more invalid UTF-8 sequences than a lossy decode allows
+/// - Embedded NUL bytes (when rejected by the converter's `NulPolicy`)
+/// - Non-printable control characters in text required to be printable
+/// - Forbidden byte sequences found by a content filter
+/// - Bodies exceeding a size limit
+/// - Bodies arriving slower than a configured minimum rate
+/// - Bodies not received within a configured timeout
+/// - Payloads that fail checksum verification
+/// - Failures in user-supplied chunk transforms
+/// - Bodies whose length differs from the declared length (with a strict `LengthCheck`)
+/// - Bodies that are not a single chunk when one is required
+/// - Malformed message framing
+/// - Malformed raw header blocks
+/// - Malformed lines in `KEY=VALUE` environment files
+/// - Invalid converter configuration, such as a zero buffer size
+/// - Failures of a named body among several collected together
+/// - Malformed compressed data (with the `gzip` feature)
+/// 
+/// When a converter is configured with `with_error_preview(true)`, decoding
+/// errors are wrapped in `WithPreview` together with a hex dump of the
+/// leading bytes of the offending data.
 /// 
 /// # Examples
 /// 
@@ -26,6 +52,7 @@ use hyper::Error as HyperError;
 ///         Err(StreamConverterError::EncodingError(e)) => eprintln!("Invalid UTF-8: {}", e),
 ///         Err(StreamConverterError::IoError(e)) => eprintln!("IO Error: {}", e),
 ///         Err(StreamConverterError::HyperError(e)) => eprintln!("Hyper Error: {}", e),
+///         Err(e) => eprintln!("Error: {}", e),
 ///     }
 /// }
 /// ```
@@ -37,6 +64,145 @@ pub enum StreamConverterError {
     EncodingError(FromUtf8Error),
     /// Represents errors that occur in the Hyper HTTP client
     HyperError(HyperError),
+    /// Represents errors from a body type other than `hyper::Body`
+    BodyError(Box<dyn Error + Send + Sync>),
+    /// Represents errors that occur when deserializing a CBOR payload
+    #[cfg(feature = "cbor")]
+    CborError(ciborium::de::Error<io::Error>),
+    /// Represents errors that occur when deserializing a JSON payload
+    #[cfg(feature = "json")]
+    JsonError(serde_json::Error),
+    /// Represents errors that occur while reading a reqwest response
+    #[cfg(feature = "reqwest")]
+    ReqwestError(reqwest::Error),
+    /// Represents text with more invalid UTF-8 sequences than a lossy decode allows
+    TooManyReplacements {
+        /// The maximum number of replacements allowed
+        max: usize,
+    },
+    /// Represents an embedded NUL byte rejected by `NulPolicy::Error`
+    NulByte {
+        /// The byte offset of the first NUL in the body
+        position: usize,
+    },
+    /// Represents a non-printable control character in text required to be printable
+    NonPrintable {
+        /// The offending character
+        codepoint: char,
+        /// The byte offset of the character in the decoded text
+        position: usize,
+    },
+    /// Represents a forbidden byte sequence found while filtering a body
+    PolicyViolation {
+        /// The forbidden token that was matched
+        token: Vec<u8>,
+    },
+    /// Represents a body that is larger than the allowed size
+    SizeLimitExceeded {
+        /// The maximum number of bytes allowed
+        limit: usize,
+        /// The number of bytes received when the limit was exceeded
+        read_so_far: usize,
+    },
+    /// Represents a payload whose checksum or footer failed verification
+    ChecksumMismatch,
+    /// Represents errors returned by a user-supplied chunk transform
+    TransformError(Box<dyn Error + Send + Sync>),
+    /// Represents a body that arrived slower than the configured minimum rate
+    TooSlow {
+        /// The average rate observed over the window, in bytes per second
+        bytes_per_sec: u64,
+        /// The configured minimum rate, in bytes per second
+        min_bytes_per_sec: u64,
+    },
+    /// Represents a body that was not fully received within the configured timeout
+    Timeout {
+        /// The configured timeout
+        timeout: Duration,
+    },
+    /// Represents a body that ended before its declared length was reached
+    UnexpectedEof {
+        /// The declared length of the body
+        expected: u64,
+        /// The number of bytes actually received
+        received: u64,
+    },
+    /// Represents a body that produced more data than its declared length
+    ExtraData {
+        /// The declared length of the body
+        expected: u64,
+        /// The number of bytes received when the excess was detected
+        received: u64,
+    },
+    /// Represents a body that did not consist of exactly one chunk
+    NotSingleChunk {
+        /// The number of chunks seen: 0 for an empty body, or 2 once a second chunk arrives
+        chunks: usize,
+    },
+    /// Represents malformed framing in a message-oriented body
+    FramingError(String),
+    /// Represents a malformed line in a raw header block
+    HeaderError(String),
+    /// Represents a malformed line in a `KEY=VALUE` environment file
+    EnvSyntaxError {
+        /// The 1-based number of the offending line
+        line: usize,
+        /// The content of the offending line
+        content: String,
+    },
+    /// Represents a buffer size the converter cannot operate with
+    InvalidBufferSize {
+        /// The rejected buffer size
+        size: usize,
+    },
+    /// Identifies which of several named bodies failed
+    NamedBodyError {
+        /// The name of the body that failed
+        name: String,
+        /// The error that body produced
+        error: Box<StreamConverterError>,
+    },
+    /// Represents malformed compressed data
+    #[cfg(feature = "gzip")]
+    DecompressionError(io::Error),
+    /// Wraps a decoding error with a hex preview of the first bytes of the data
+    WithPreview {
+        /// The underlying decoding error
+        error: Box<StreamConverterError>,
+        /// Hex dump of the leading bytes of the data that failed to decode
+        preview: String,
+    },
+}
+
+impl StreamConverterError {
+    /// Converts the error of an `http_body::Body`, keeping Hyper errors as
+    /// `HyperError` (and reqwest errors as `ReqwestError`).
+    pub(crate) fn from_body_error<E>(error: E) -> Self
+    where
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let error = match error.into().downcast::<HyperError>() {
+            Ok(error) => return StreamConverterError::HyperError(*error),
+            Err(error) => error,
+        };
+        #[cfg(feature = "reqwest")]
+        let error = match error.downcast::<reqwest::Error>() {
+            Ok(error) => return StreamConverterError::ReqwestError(*error),
+            Err(error) => error,
+        };
+        StreamConverterError::BodyError(error)
+    }
+
+    /// Wraps the error with `preview`, if one is given.
+    pub(crate) fn with_preview(self, preview: Option<String>) -> Self {
+        match preview {
+            Some(preview) => StreamConverterError::WithPreview {
+                error: Box::new(self),
+                preview,
+            },
+            None => self,
+        }
+    }
 }
 
 impl fmt::Display for StreamConverterError {
@@ -45,6 +211,66 @@ impl fmt::Display for StreamConverterError {
             StreamConverterError::IoError(e) => write!(f, "IO error: {}", e),
             StreamConverterError::EncodingError(e) => write!(f, "Encoding error: {}", e),
             StreamConverterError::HyperError(e) => write!(f, "Hyper error: {}", e),
+            StreamConverterError::BodyError(e) => write!(f, "Body error: {}", e),
+            #[cfg(feature = "cbor")]
+            StreamConverterError::CborError(e) => write!(f, "CBOR error: {}", e),
+            #[cfg(feature = "json")]
+            StreamConverterError::JsonError(e) => write!(f, "JSON error: {}", e),
+            #[cfg(feature = "reqwest")]
+            StreamConverterError::ReqwestError(e) => write!(f, "Reqwest error: {}", e),
+            StreamConverterError::TooManyReplacements { max } => {
+                write!(f, "More than {} invalid UTF-8 sequences replaced", max)
+            }
+            StreamConverterError::NulByte { position } => {
+                write!(f, "Embedded NUL byte at position {}", position)
+            }
+            StreamConverterError::NonPrintable { codepoint, position } => {
+                write!(f, "Non-printable character {:?} at position {}", codepoint, position)
+            }
+            StreamConverterError::PolicyViolation { token } => {
+                write!(f, "Forbidden token \"{}\" found in body", token.escape_ascii())
+            }
+            StreamConverterError::SizeLimitExceeded { limit, read_so_far } => {
+                write!(f, "Size limit exceeded: read {} bytes, limit is {}", read_so_far, limit)
+            }
+            StreamConverterError::ChecksumMismatch => write!(f, "Checksum mismatch"),
+            StreamConverterError::TransformError(e) => write!(f, "Transform error: {}", e),
+            StreamConverterError::TooSlow { bytes_per_sec, min_bytes_per_sec } => write!(
+                f,
+                "Body too slow: {} bytes/sec, minimum is {}",
+                bytes_per_sec, min_bytes_per_sec
+            ),
+            StreamConverterError::Timeout { timeout } => {
+                write!(f, "Body not received within {:?}", timeout)
+            }
+            StreamConverterError::UnexpectedEof { expected, received } => {
+                write!(f, "Unexpected end of body: expected {} bytes, received {}", expected, received)
+            }
+            StreamConverterError::ExtraData { expected, received } => {
+                write!(f, "Extra data in body: expected {} bytes, received {}", expected, received)
+            }
+            StreamConverterError::NotSingleChunk { chunks: 0 } => {
+                write!(f, "Expected a single chunk, but the body was empty")
+            }
+            StreamConverterError::NotSingleChunk { .. } => {
+                write!(f, "Expected a single chunk, but the body had more than one")
+            }
+            StreamConverterError::FramingError(msg) => write!(f, "Framing error: {}", msg),
+            StreamConverterError::HeaderError(msg) => write!(f, "Header error: {}", msg),
+            StreamConverterError::EnvSyntaxError { line, content } => {
+                write!(f, "Invalid environment line {}: {:?}", line, content)
+            }
+            StreamConverterError::InvalidBufferSize { size } => {
+                write!(f, "Invalid buffer size: {}", size)
+            }
+            StreamConverterError::NamedBodyError { name, error } => {
+                write!(f, "Body {:?} failed: {}", name, error)
+            }
+            #[cfg(feature = "gzip")]
+            StreamConverterError::DecompressionError(e) => write!(f, "Decompression error: {}", e),
+            StreamConverterError::WithPreview { error, preview } => {
+                write!(f, "{} (data starts with: {})", error, preview)
+            }
         }
     }
 }
@@ -55,7 +281,59 @@ impl Error for StreamConverterError {
             StreamConverterError::IoError(e) => Some(e),
             StreamConverterError::EncodingError(e) => Some(e),
             StreamConverterError::HyperError(e) => Some(e),
+            StreamConverterError::BodyError(e) => Some(e.as_ref()),
+            #[cfg(feature = "cbor")]
+            StreamConverterError::CborError(e) => Some(e),
+            #[cfg(feature = "json")]
+            StreamConverterError::JsonError(e) => Some(e),
+            #[cfg(feature = "reqwest")]
+            StreamConverterError::ReqwestError(e) => Some(e),
+            StreamConverterError::TooManyReplacements { .. } => None,
+            StreamConverterError::NulByte { .. } => None,
+            StreamConverterError::NonPrintable { .. } => None,
+            StreamConverterError::PolicyViolation { .. } => None,
+            StreamConverterError::SizeLimitExceeded { .. } => None,
+            StreamConverterError::ChecksumMismatch => None,
+            StreamConverterError::TransformError(e) => Some(e.as_ref()),
+            StreamConverterError::TooSlow { .. } => None,
+            StreamConverterError::Timeout { .. } => None,
+            StreamConverterError::UnexpectedEof { .. } => None,
+            StreamConverterError::ExtraData { .. } => None,
+            StreamConverterError::NotSingleChunk { .. } => None,
+            StreamConverterError::FramingError(_) => None,
+            StreamConverterError::HeaderError(_) => None,
+            StreamConverterError::EnvSyntaxError { .. } => None,
+            StreamConverterError::InvalidBufferSize { .. } => None,
+            StreamConverterError::NamedBodyError { error, .. } => Some(error.as_ref()),
+            #[cfg(feature = "gzip")]
+            StreamConverterError::DecompressionError(e) => Some(e),
+            StreamConverterError::WithPreview { error, .. } => Some(error.as_ref()),
         }
     }
 }
 
+
+impl From<io::Error> for StreamConverterError {
+    fn from(error: io::Error) -> Self {
+        StreamConverterError::IoError(error)
+    }
+}
+
+impl From<FromUtf8Error> for StreamConverterError {
+    fn from(error: FromUtf8Error) -> Self {
+        StreamConverterError::EncodingError(error)
+    }
+}
+
+impl From<HyperError> for StreamConverterError {
+    fn from(error: HyperError) -> Self {
+        StreamConverterError::HyperError(error)
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl From<reqwest::Error> for StreamConverterError {
+    fn from(error: reqwest::Error) -> Self {
+        StreamConverterError::ReqwestError(error)
+    }
+}
@@ -57,14 +57,18 @@
 //!         Err(StreamConverterError::EncodingError(e)) => eprintln!("Encoding error: {}", e),
 //!         Err(StreamConverterError::IoError(e)) => eprintln!("IO error: {}", e),
 //!         Err(StreamConverterError::HyperError(e)) => eprintln!("Hyper error: {}", e),
+//!         Err(e) => eprintln!("Error: {}", e),
 //!     }
 //! }
 //! ```
 
+pub mod body;
 pub mod converter;
 pub mod error;
 pub mod process;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod util;
 
-pub use converter::StreamConverter;
+pub use converter::{StreamConverter, StreamConverterBuilder};
 pub use error::StreamConverterError;
@@ -3,18 +3,24 @@
 //! This module contains functions for processing streams without needing to create
 //! a StreamConverter instance. Useful for one-off stream processing operations.
 
+use std::error::Error;
+use std::sync::OnceLock;
+
+use crate::converter::StreamConverter;
 use crate::error::StreamConverterError;
-use futures_util::StreamExt;
-use hyper::Body;
+use bytes::BufMut;
+use futures_core::Stream;
+use hyper::body::HttpBody;
 
 /// Processes a Hyper response body into a vector of bytes.
 /// 
 /// This is a convenience function that processes a Hyper body without requiring
 /// a StreamConverter instance. It's useful for simple, one-off conversions.
+/// Any `http_body::Body` whose error converts into a boxed error is accepted.
 /// 
 /// # Arguments
 /// 
-/// * `body` - The Hyper response body to process
+/// * `body` - The Hyper response body (or other `http_body::Body`) to process
 /// 
 /// # Returns
 /// 
@@ -34,11 +40,67 @@ use hyper::Body;
 ///     Ok(())
 /// }
 /// ```
-pub async fn process_stream(mut body: Body) -> Result<Vec<u8>, StreamConverterError> {
+pub async fn process_stream<B>(mut body: B) -> Result<Vec<u8>, StreamConverterError>
+where
+    B: HttpBody + Unpin,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
     let mut bytes = Vec::new();
-    while let Some(chunk) = body.next().await {
-        let chunk = chunk.map_err(StreamConverterError::HyperError)?;
-        bytes.extend_from_slice(&chunk);
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(StreamConverterError::from_body_error)?;
+        bytes.put(chunk);
     }
     Ok(bytes)
-} 
\ No newline at end of file
+}
+
+/// Streams a Hyper response body as lines of text.
+/// 
+/// Incoming chunks are buffered and split on `\n` (a preceding `\r` is also
+/// removed), and each complete line is yielded as soon as it arrives. A partial
+/// trailing line is held back until more data arrives, and any content left
+/// at the end of the body is emitted as a final line. This suits
+/// newline-delimited JSON and similar endpoints that should not be buffered
+/// in full. This is `StreamConverter::body_lines` with default settings; use
+/// that method directly for configurable behavior.
+/// 
+/// # Arguments
+/// 
+/// * `body` - The Hyper response body (or other `http_body::Body`) to split into lines
+/// 
+/// # Returns
+/// 
+/// A stream yielding each line, or a StreamConverterError if the body fails
+/// or a line is not valid UTF-8
+/// 
+/// # Examples
+/// 
+/// ```rust,no_run
+/// use b_stb::process::body_lines;
+/// use futures_util::StreamExt;
+/// use hyper::Body;
+/// 
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let body = Body::from("{\"id\": 1}\n{\"id\": 2}\n");
+///     let mut lines = Box::pin(body_lines(body));
+///     
+///     while let Some(line) = lines.next().await {
+///         println!("line: {}", line?);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn body_lines<'a, B>(body: B) -> impl Stream<Item = Result<String, StreamConverterError>> + 'a
+where
+    B: HttpBody + Unpin + 'a,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    default_converter().body_lines(body)
+}
+
+/// Returns a shared converter with default settings, for functions that
+/// delegate to `StreamConverter`.
+fn default_converter() -> &'static StreamConverter {
+    static CONVERTER: OnceLock<StreamConverter> = OnceLock::new();
+    CONVERTER.get_or_init(StreamConverter::new)
+}
@@ -0,0 +1,59 @@
+//! Helpers for reproducing streaming behavior in tests.
+//! 
+//! These functions are available with the `testing` feature. They build
+//! streams that can be wrapped with `Body::wrap_stream` to replay recorded
+//! traffic deterministically.
+
+use std::convert::Infallible;
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures_core::Stream;
+use futures_util::stream;
+
+/// Creates a stream that replays recorded chunks at their original pace.
+/// 
+/// Each entry is `(delay, chunk)`, where `delay` is how long to wait after the
+/// previous chunk (or after the stream is first polled) before emitting
+/// `chunk`. The delays use Tokio's timer, so tests running with a paused clock
+/// replay instantly while still observing the recorded timing.
+/// 
+/// # Arguments
+/// 
+/// * `data` - The recorded delays and chunks, in order
+/// 
+/// # Returns
+/// 
+/// A stream yielding each chunk after its delay, suitable for `Body::wrap_stream`
+/// 
+/// # Examples
+/// 
+/// ```rust,no_run
+/// use b_stb::testing::body_to_bytes_paced;
+/// use b_stb::StreamConverter;
+/// use bytes::Bytes;
+/// use hyper::Body;
+/// use std::time::Duration;
+/// 
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let recording = vec![
+///         (Duration::from_millis(0), Bytes::from("first")),
+///         (Duration::from_millis(250), Bytes::from(" second")),
+///     ];
+///     let body = Body::wrap_stream(body_to_bytes_paced(recording));
+///     
+///     let content = StreamConverter::new().body_to_string(body).await?;
+///     assert_eq!(content, "first second");
+///     Ok(())
+/// }
+/// ```
+pub fn body_to_bytes_paced(
+    data: Vec<(Duration, Bytes)>,
+) -> impl Stream<Item = Result<Bytes, Infallible>> + Send + 'static {
+    stream::unfold(data.into_iter(), |mut data| async move {
+        let (delay, chunk) = data.next()?;
+        tokio::time::sleep(delay).await;
+        Some((Ok(chunk), data))
+    })
+}
@@ -3,7 +3,9 @@
 //! This module provides helper functions for common operations like
 //! converting bytes to strings and concatenating byte chunks.
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
+
+use crate::error::StreamConverterError;
 
 /// Converts a byte slice to a UTF-8 string.
 /// 
@@ -35,7 +37,8 @@ pub fn bytes_to_string(bytes: &[u8]) -> Option<String> {
 /// Concatenates multiple byte chunks into a single vector.
 /// 
 /// This function efficiently combines multiple `Bytes` chunks into a single
-/// contiguous vector of bytes.
+/// contiguous vector of bytes. The total length is computed up front so the
+/// result is allocated exactly once.
 /// 
 /// # Arguments
 /// 
@@ -61,13 +64,48 @@ pub fn bytes_to_string(bytes: &[u8]) -> Option<String> {
 /// assert_eq!(&combined, b"Hello, World!");
 /// ```
 pub fn concat_bytes(chunks: Vec<Bytes>) -> Vec<u8> {
-    let mut result = Vec::new();
+    let total = chunks.iter().map(Bytes::len).sum();
+    let mut result = Vec::with_capacity(total);
     for chunk in chunks {
         result.extend_from_slice(&chunk);
     }
     result
 }
 
+/// Concatenates multiple byte chunks into a single `Bytes`.
+/// 
+/// Like `concat_bytes`, the result is allocated exactly once. A single chunk
+/// is returned unchanged without copying, and no chunks yield an empty `Bytes`.
+/// 
+/// # Arguments
+/// 
+/// * `chunks` - A vector of Bytes chunks to concatenate
+/// 
+/// # Returns
+/// 
+/// A `Bytes` containing all bytes from the input chunks
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use b_stb::util::concat_to_bytes;
+/// use bytes::Bytes;
+/// 
+/// let chunks = vec![Bytes::from("Hello"), Bytes::from(", World!")];
+/// assert_eq!(concat_to_bytes(chunks), Bytes::from("Hello, World!"));
+/// ```
+pub fn concat_to_bytes(mut chunks: Vec<Bytes>) -> Bytes {
+    if chunks.len() == 1 {
+        return chunks.pop().unwrap_or_default();
+    }
+    let total = chunks.iter().map(Bytes::len).sum();
+    let mut result = BytesMut::with_capacity(total);
+    for chunk in chunks {
+        result.extend_from_slice(&chunk);
+    }
+    result.freeze()
+}
+
 /// Returns the default buffer size used by StreamConverter.
 /// 
 /// This function returns the recommended buffer size for stream operations.
@@ -87,4 +125,231 @@ pub fn concat_bytes(chunks: Vec<Bytes>) -> Vec<u8> {
 /// ```
 pub fn default_buffer_size() -> usize {
     8192 // 8KB default buffer size
-} 
\ No newline at end of file
+} 
+
+/// Parses a raw HTTP header block into name/value pairs.
+/// 
+/// The block is split on CRLF and each line is parsed as `Name: value`, with
+/// surrounding whitespace trimmed from the value. Header order and duplicate
+/// headers are preserved. Empty lines (such as the blank line ending a header
+/// block) are skipped.
+/// 
+/// # Arguments
+/// 
+/// * `bytes` - The raw header block to parse
+/// 
+/// # Returns
+/// 
+/// A Result containing the parsed headers in order, or a StreamConverterError
+/// describing the first invalid line
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use b_stb::util::parse_header_block;
+/// 
+/// let headers = parse_header_block(b"Accept: text/html\r\nAccept: */*\r\n\r\n").unwrap();
+/// assert_eq!(headers, vec![
+///     ("Accept".to_string(), "text/html".to_string()),
+///     ("Accept".to_string(), "*/*".to_string()),
+/// ]);
+/// ```
+pub fn parse_header_block(bytes: &[u8]) -> Result<Vec<(String, String)>, StreamConverterError> {
+    let block = String::from_utf8(bytes.to_vec())
+        .map_err(StreamConverterError::EncodingError)?;
+
+    let mut headers = Vec::new();
+    for line in block.split("\r\n") {
+        if line.is_empty() {
+            continue;
+        }
+        let (name, value) = line
+            .split_once(':')
+            .filter(|(name, _)| !name.is_empty() && !name.contains(char::is_whitespace))
+            .ok_or_else(|| StreamConverterError::HeaderError(format!("invalid header line: {:?}", line)))?;
+        headers.push((name.to_string(), value.trim().to_string()));
+    }
+    Ok(headers)
+}
+
+/// Splits a byte buffer into lines without allocating.
+/// 
+/// Lines are separated by `\n` or `\r\n`, and the yielded slices borrow from
+/// `bytes` with the line ending removed. A trailing newline does not produce
+/// an extra empty line, and an empty buffer yields no lines.
+/// 
+/// # Arguments
+/// 
+/// * `bytes` - The buffer to split
+/// 
+/// # Returns
+/// 
+/// An iterator over the lines of `bytes`
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use b_stb::util::lines_borrowed;
+/// 
+/// let lines: Vec<&[u8]> = lines_borrowed(b"one\r\ntwo\n").collect();
+/// assert_eq!(lines, vec![&b"one"[..], &b"two"[..]]);
+/// ```
+pub fn lines_borrowed(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let content = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+    (!bytes.is_empty())
+        .then(|| content.split(|&b| b == b'\n'))
+        .into_iter()
+        .flatten()
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+}
+
+/// Known file signatures as `(offset, magic bytes, extension)`.
+const SIGNATURES: &[(usize, &[u8], &str)] = &[
+    (0, b"\x89PNG\r\n\x1a\n", "png"),
+    (0, b"\xff\xd8\xff", "jpg"),
+    (0, b"GIF87a", "gif"),
+    (0, b"GIF89a", "gif"),
+    (0, b"%PDF-", "pdf"),
+    (0, b"\x1f\x8b", "gz"),
+    (0, b"PK\x03\x04", "zip"),
+    (0, b"PK\x05\x06", "zip"),
+    (0, b"BZh", "bz2"),
+    (0, b"\xfd7zXZ\x00", "xz"),
+    (0, b"\x28\xb5\x2f\xfd", "zst"),
+    (0, b"7z\xbc\xaf\x27\x1c", "7z"),
+    (0, b"\x00asm", "wasm"),
+    (0, b"OggS", "ogg"),
+    (0, b"fLaC", "flac"),
+    (257, b"ustar", "tar"),
+];
+
+/// Known DIB header sizes of BMP files, from `BITMAPCOREHEADER` to `BITMAPV5HEADER`.
+const BMP_DIB_HEADER_SIZES: &[u32] = &[12, 40, 52, 56, 64, 108, 124];
+
+/// Largest `ftyp` box size accepted, far above the few dozen bytes real files use.
+const MAX_FTYP_BOX_SIZE: u32 = 4096;
+
+/// Reads a little-endian `u32` at `offset`, if `bytes` is long enough.
+fn read_u32_le(bytes: &[u8], offset: usize) -> Option<u32> {
+    let field = bytes.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes(field.try_into().ok()?))
+}
+
+/// Returns true if `bytes` starts with a plausible BMP file header.
+/// 
+/// The `BM` magic alone matches ordinary text, so the reserved fields must be
+/// zero, the declared file size must fit the headers (or be zero, as some
+/// writers leave it), the pixel data must start after the headers, and the DIB
+/// header must have a known size.
+fn is_bmp(bytes: &[u8]) -> bool {
+    let (Some(file_size), Some(reserved), Some(data_offset), Some(dib_size)) = (
+        read_u32_le(bytes, 2),
+        read_u32_le(bytes, 6),
+        read_u32_le(bytes, 10),
+        read_u32_le(bytes, 14),
+    ) else {
+        return false;
+    };
+    bytes.starts_with(b"BM")
+        && reserved == 0
+        && BMP_DIB_HEADER_SIZES.contains(&dib_size)
+        && data_offset >= 14 + dib_size
+        && (file_size == 0 || file_size >= data_offset)
+}
+
+/// Returns true if `bytes` is a RIFF container holding the given form type.
+/// 
+/// The form type sits at offset 8, where it can just as well appear in text, so
+/// the `RIFF` magic must also be present at offset 0.
+fn is_riff(bytes: &[u8], form: &[u8; 4]) -> bool {
+    bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(form)
+}
+
+/// Returns true if `bytes` starts with a plausible ISO base media `ftyp` box.
+/// 
+/// The `ftyp` type sits at offset 4, so the box size before it must cover the
+/// major brand and minor version, be a whole number of compatible brands, and
+/// stay small (text before `ftyp` reads as a size in the hundreds of megabytes),
+/// and the major brand must be printable ASCII.
+fn is_ftyp(bytes: &[u8]) -> bool {
+    let Some(header) = bytes.get(..12) else {
+        return false;
+    };
+    let box_size = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+    &header[4..8] == b"ftyp"
+        && (16..=MAX_FTYP_BOX_SIZE).contains(&box_size)
+        && box_size % 4 == 0
+        && header[8..12].iter().all(|&b| b.is_ascii_alphanumeric() || b == b' ')
+}
+
+/// Returns true if `bytes` starts with a plausible ID3v2 tag header.
+/// 
+/// The `ID3` magic alone matches ordinary text, so the major version must be
+/// 2, 3, or 4, the revision must not be `0xFF`, the undefined low flag bits
+/// must be clear, and the tag size must be a valid syncsafe integer.
+fn is_id3(bytes: &[u8]) -> bool {
+    let Some(header) = bytes.get(..10) else {
+        return false;
+    };
+    header.starts_with(b"ID3")
+        && (2..=4).contains(&header[3])
+        && header[4] != 0xFF
+        && header[5] & 0x0F == 0
+        && header[6..10].iter().all(|&b| b < 0x80)
+}
+
+/// Guesses a file extension from the leading (magic) bytes of `bytes`.
+/// 
+/// Common image, document, archive, and media formats are recognized. Formats
+/// whose magic bytes are short enough to occur in text (BMP and MP3 with an
+/// ID3 tag) or sit past the start of the file (WebP, WAV, and MP4) are only
+/// recognized when the rest of their header is valid.
+/// Text formats have no reliable signature and are not detected.
+/// 
+/// # Arguments
+/// 
+/// * `bytes` - The data to inspect, or at least its first few hundred bytes
+/// 
+/// # Returns
+/// 
+/// Some(extension) without a leading dot if a known signature matches, None otherwise
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use b_stb::util::guess_extension;
+/// 
+/// assert_eq!(guess_extension(b"%PDF-1.7 ..."), Some("pdf"));
+/// assert_eq!(guess_extension(b"plain text"), None);
+/// ```
+pub fn guess_extension(bytes: &[u8]) -> Option<&'static str> {
+    SIGNATURES
+        .iter()
+        .find(|(offset, magic, _)| {
+            bytes.get(*offset..).is_some_and(|rest| rest.starts_with(magic))
+        })
+        .map(|&(_, _, extension)| extension)
+        .or_else(|| {
+            if is_bmp(bytes) {
+                Some("bmp")
+            } else if is_id3(bytes) {
+                Some("mp3")
+            } else if is_riff(bytes, b"WEBP") {
+                Some("webp")
+            } else if is_riff(bytes, b"WAVE") {
+                Some("wav")
+            } else if is_ftyp(bytes) {
+                Some("mp4")
+            } else {
+                None
+            }
+        })
+}
+
+/// Returns the position of the first occurrence of `needle` in `haystack`.
+pub(crate) fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
@@ -0,0 +1,40 @@
+use b_stb::body::MeteredBody;
+use bytes::Bytes;
+use hyper::Body;
+use std::sync::atomic::Ordering;
+
+#[tokio::test]
+async fn test_metered_body_counts_bytes() {
+    let chunks = vec![Bytes::from("Hello"), Bytes::from(", World!")];
+    let inner = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let body = MeteredBody::new(inner);
+    let counter = body.counter();
+
+    let data = hyper::body::to_bytes(body).await.unwrap();
+    assert_eq!(&data[..], b"Hello, World!");
+    assert_eq!(counter.load(Ordering::Relaxed), 13);
+}
+
+#[cfg(feature = "testing")]
+#[tokio::test(start_paused = true)]
+async fn test_body_to_bytes_paced() {
+    use b_stb::testing::body_to_bytes_paced;
+    use std::time::Duration;
+    use tokio::time::Instant;
+
+    let recording = vec![
+        (Duration::from_millis(100), Bytes::from("a")),
+        (Duration::from_millis(0), Bytes::from("b")),
+        (Duration::from_millis(400), Bytes::from("c")),
+    ];
+    let start = Instant::now();
+    let (bytes, frames) = b_stb::StreamConverter::new()
+        .body_to_bytes_with_frames(Body::wrap_stream(body_to_bytes_paced(recording)))
+        .await
+        .unwrap();
+    assert_eq!(bytes, b"abc");
+    assert_eq!(frames, vec![1, 1, 1]);
+    assert_eq!(start.elapsed(), Duration::from_millis(500));
+}
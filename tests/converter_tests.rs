@@ -55,4 +55,2629 @@ async fn test_chunked_body() {
     
     let result = converter.body_to_string(body).await.unwrap();
     assert_eq!(result, "Hello, World!");
-} 
\ No newline at end of file
+} 
+
+#[cfg(feature = "digest")]
+#[tokio::test]
+async fn test_body_to_bytes_with_hasher_state() {
+    let converter = StreamConverter::new();
+    let body = Body::from("abc");
+
+    let (bytes, digest) = converter.body_to_bytes_with_hasher_state(body).await.unwrap();
+    let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    assert_eq!(bytes, b"abc");
+    assert_eq!(hex, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+}
+
+#[cfg(feature = "digest")]
+#[tokio::test]
+async fn test_body_to_bytes_hashed_with_prefix() {
+    use b_stb::converter::HasherState;
+
+    let converter = StreamConverter::new();
+    let mut state = HasherState::new().prefix(b"a");
+    let bytes = converter.body_to_bytes_hashed(Body::from("bc"), &mut state).await.unwrap();
+
+    let expected = HasherState::new().prefix(b"abc").finalize();
+    assert_eq!(bytes, b"bc");
+    assert_eq!(state.finalize(), expected);
+}
+
+#[tokio::test]
+async fn test_soft_limit_callback() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let seen = Arc::new(AtomicUsize::new(0));
+    let (calls_cb, seen_cb) = (calls.clone(), seen.clone());
+    let converter = StreamConverter::new().with_soft_limit(6, move |size| {
+        calls_cb.fetch_add(1, Ordering::SeqCst);
+        seen_cb.store(size, Ordering::SeqCst);
+    });
+
+    let chunks = vec![
+        Bytes::from("Hello"),
+        Bytes::from(", "),
+        Bytes::from("World!"),
+    ];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+
+    let result = converter.body_to_string(body).await.unwrap();
+    assert_eq!(result, "Hello, World!");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(seen.load(Ordering::SeqCst), 7);
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_mut() {
+    let converter = StreamConverter::new();
+    let mut dst = bytes::BytesMut::from("existing|");
+
+    let added = converter.body_to_bytes_mut(Body::from("appended"), &mut dst).await.unwrap();
+    assert_eq!(added, 8);
+    assert_eq!(&dst[..], b"existing|appended");
+}
+
+#[cfg(feature = "cbor")]
+#[tokio::test]
+async fn test_body_to_cbor() {
+    let converter = StreamConverter::new();
+    let body = Body::from(vec![0x83, 0x01, 0x02, 0x03]); // [1, 2, 3]
+
+    let result: Vec<u32> = converter.body_to_cbor(body).await.unwrap();
+    assert_eq!(result, vec![1, 2, 3]);
+}
+
+#[cfg(feature = "cbor")]
+#[tokio::test]
+async fn test_body_to_cbor_invalid() {
+    let converter = StreamConverter::new();
+    let body = Body::from(vec![0x83, 0x01]); // truncated array
+
+    let result = converter.body_to_cbor::<Vec<u32>>(body).await;
+    assert!(matches!(result, Err(b_stb::StreamConverterError::CborError(_))));
+}
+
+#[tokio::test]
+async fn test_error_preview() {
+    let converter = StreamConverter::new().with_error_preview(true);
+    let body = Body::from(vec![0x41, 0xFF, 0x42]);
+
+    let err = converter.body_to_string(body).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::WithPreview { .. }));
+    assert!(err.to_string().contains("41 ff 42"));
+}
+
+#[tokio::test]
+async fn test_error_preview_disabled_by_default() {
+    let converter = StreamConverter::new();
+    let body = Body::from(vec![0x41, 0xFF, 0x42]);
+
+    let err = converter.body_to_string(body).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::EncodingError(_)));
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_xored_across_chunks() {
+    let converter = StreamConverter::new();
+    let key = [0x10, 0x20, 0x30];
+    let plain = b"Hello, World!";
+    let chunks = vec![
+        Bytes::from_static(&plain[..4]),
+        Bytes::from_static(&plain[4..9]),
+        Bytes::from_static(&plain[9..]),
+    ];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+
+    let result = converter.body_to_bytes_xored(body, &key).await.unwrap();
+    let expected: Vec<u8> = plain.iter().enumerate().map(|(i, b)| b ^ key[i % 3]).collect();
+    assert_eq!(result, expected);
+}
+
+#[tokio::test]
+async fn test_body_to_string_with_line_count() {
+    let converter = StreamConverter::new();
+
+    let (content, lines) = converter
+        .body_to_string_with_line_count(Body::from("one\ntwo\nthree"))
+        .await
+        .unwrap();
+    assert_eq!(content, "one\ntwo\nthree");
+    assert_eq!(lines, 3);
+
+    let (_, lines) = converter
+        .body_to_string_with_line_count(Body::from("one\ntwo\n"))
+        .await
+        .unwrap();
+    assert_eq!(lines, 2);
+
+    let (_, lines) = converter.body_to_string_with_line_count(Body::empty()).await.unwrap();
+    assert_eq!(lines, 0);
+}
+
+#[tokio::test]
+async fn test_body_jsonrpc_messages_across_chunks() {
+    use futures::StreamExt;
+
+    let converter = StreamConverter::new();
+    let chunks = vec![
+        Bytes::from("Content-Len"),
+        Bytes::from("gth: 7\r\n\r\n{\"a\":"),
+        Bytes::from("1}Content-Length: 2\r\nContent-Type: application/json\r\n\r\n"),
+        Bytes::from("[]"),
+    ];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+
+    let messages: Vec<_> = converter.body_jsonrpc_messages(body).collect().await;
+    let messages: Vec<Vec<u8>> = messages.into_iter().map(Result::unwrap).collect();
+    assert_eq!(messages, vec![b"{\"a\":1}".to_vec(), b"[]".to_vec()]);
+}
+
+#[tokio::test]
+async fn test_body_jsonrpc_messages_truncated() {
+    use futures::StreamExt;
+
+    let converter = StreamConverter::new();
+    let body = Body::from("Content-Length: 10\r\n\r\n{}");
+
+    let messages: Vec<_> = converter.body_jsonrpc_messages(body).collect().await;
+    assert_eq!(messages.len(), 1);
+    assert!(matches!(messages[0], Err(b_stb::StreamConverterError::FramingError(_))));
+}
+
+#[tokio::test]
+async fn test_body_lines_trailing_newline_policy() {
+    use b_stb::converter::TrailingNewline;
+    use futures::TryStreamExt;
+
+    let converter = StreamConverter::new();
+    let lines: Vec<String> = converter.body_lines(Body::from("a\r\nb\n")).try_collect().await.unwrap();
+    assert_eq!(lines, vec!["a", "b"]);
+
+    let converter = StreamConverter::new().with_trailing_newline(TrailingNewline::EmitEmpty);
+    let lines: Vec<String> = converter.body_lines(Body::from("a\nb\n")).try_collect().await.unwrap();
+    assert_eq!(lines, vec!["a", "b", ""]);
+
+    let lines: Vec<String> = converter.body_lines(Body::from("a\nb")).try_collect().await.unwrap();
+    assert_eq!(lines, vec!["a", "b"]);
+}
+
+#[tokio::test]
+async fn test_body_to_headers() {
+    let converter = StreamConverter::new();
+    let body = Body::from("Accept: text/html\r\nAccept: */*\r\n");
+
+    let headers = converter.body_to_headers(body).await.unwrap();
+    assert_eq!(headers.len(), 2);
+    assert_eq!(headers[1], ("Accept".to_string(), "*/*".to_string()));
+}
+
+#[tokio::test]
+async fn test_body_to_slice() {
+    let converter = StreamConverter::new();
+    let mut scratch = [0u8; 16];
+
+    let data = converter.body_to_slice(Body::from("fits"), &mut scratch).await.unwrap();
+    assert_eq!(data, b"fits");
+
+    let err = converter
+        .body_to_slice(Body::from("this does not fit"), &mut scratch)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        b_stb::StreamConverterError::SizeLimitExceeded { limit: 16, read_so_far: 17 }
+    ));
+}
+
+#[tokio::test]
+async fn test_body_transform() {
+    use b_stb::converter::ChunkTransform;
+    use futures::TryStreamExt;
+
+    struct Uppercase {
+        chunks: usize,
+    }
+
+    impl ChunkTransform for Uppercase {
+        type Error = std::io::Error;
+
+        async fn transform(&mut self, chunk: Bytes) -> Result<Bytes, Self::Error> {
+            self.chunks += 1;
+            Ok(Bytes::from(chunk.to_ascii_uppercase()))
+        }
+
+        async fn finish(&mut self) -> Result<Bytes, Self::Error> {
+            Ok(Bytes::from(format!(" [{} chunks]", self.chunks)))
+        }
+    }
+
+    let converter = StreamConverter::new();
+    let chunks = vec![Bytes::from("hello, "), Bytes::from("world")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+
+    let output: Vec<Bytes> = converter
+        .body_transform(body, Uppercase { chunks: 0 })
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(b_stb::util::concat_bytes(output), b"HELLO, WORLD [2 chunks]");
+}
+
+#[tokio::test]
+async fn test_line_count_and_eol_strip_bom() {
+    use b_stb::converter::LineEnding;
+
+    let converter = StreamConverter::new().with_strip_bom(true);
+    let (content, lines) = converter
+        .body_to_string_with_line_count(Body::from(&b"\xEF\xBB\xBFa\nb"[..]))
+        .await
+        .unwrap();
+    assert_eq!((content.as_str(), lines), ("a\nb", 2));
+
+    let (content, eol) = converter
+        .body_to_string_with_eol(Body::from(&b"\xEF\xBB\xBFa\r\n"[..]))
+        .await
+        .unwrap();
+    assert_eq!((content.as_str(), eol), ("a\r\n", LineEnding::CrLf));
+}
+
+#[tokio::test]
+async fn test_line_count_and_eol_apply_nul_policy() {
+    use b_stb::converter::NulPolicy;
+
+    let converter = StreamConverter::new().with_nul_policy(NulPolicy::Strip);
+    let (content, lines) = converter
+        .body_to_string_with_line_count(Body::from(&b"a\0\nb"[..]))
+        .await
+        .unwrap();
+    assert_eq!((content.as_str(), lines), ("a\nb", 2));
+    let (content, _) = converter.body_to_string_with_eol(Body::from(&b"a\0\n"[..])).await.unwrap();
+    assert_eq!(content, "a\n");
+
+    let converter = StreamConverter::new().with_nul_policy(NulPolicy::Error);
+    let err = converter
+        .body_to_string_with_line_count(Body::from(&b"ab\0"[..]))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::NulByte { position: 2 }));
+    let err = converter.body_to_string_with_eol(Body::from(&b"\0\n"[..])).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::NulByte { position: 0 }));
+}
+
+#[tokio::test]
+async fn test_body_to_string_with_eol() {
+    use b_stb::converter::LineEnding;
+
+    let converter = StreamConverter::new();
+    let chunks = vec![Bytes::from("a\r"), Bytes::from("\nb\r\n")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let (content, eol) = converter.body_to_string_with_eol(body).await.unwrap();
+    assert_eq!(content, "a\r\nb\r\n");
+    assert_eq!(eol, LineEnding::CrLf);
+
+    let (_, eol) = converter.body_to_string_with_eol(Body::from("a\nb\n")).await.unwrap();
+    assert_eq!(eol, LineEnding::Lf);
+
+    let (_, eol) = converter.body_to_string_with_eol(Body::from("a\r\nb\n")).await.unwrap();
+    assert_eq!(eol, LineEnding::Mixed);
+
+    // A stray LF among many CRLFs does not change the predominant style
+    let body = format!("{}stray\n", "line\r\n".repeat(1000));
+    let (_, eol) = converter.body_to_string_with_eol(Body::from(body)).await.unwrap();
+    assert_eq!(eol, LineEnding::CrLf);
+
+    let (_, eol) = converter.body_to_string_with_eol(Body::from("a\nb\nc\r\n")).await.unwrap();
+    assert_eq!(eol, LineEnding::Lf);
+
+    let (_, eol) = converter.body_to_string_with_eol(Body::from("ab")).await.unwrap();
+    assert_eq!(eol, LineEnding::Lf);
+}
+
+#[tokio::test]
+async fn test_collect_all() {
+    let converter = StreamConverter::new();
+    let bodies = vec![
+        ("a".to_string(), Body::from("first")),
+        ("b".to_string(), Body::from("second")),
+    ];
+
+    let results = converter.collect_all(bodies).await.unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results["a"], b"first");
+    assert_eq!(results["b"], b"second");
+}
+
+#[tokio::test]
+async fn test_collect_all_names_failing_body() {
+    let converter = StreamConverter::new();
+    let (sender, failing) = Body::channel();
+    sender.abort();
+    let bodies = vec![
+        ("ok".to_string(), Body::from("fine")),
+        ("broken".to_string(), failing),
+    ];
+
+    let err = converter.collect_all(bodies).await.unwrap_err();
+    match err {
+        b_stb::StreamConverterError::NamedBodyError { name, .. } => assert_eq!(name, "broken"),
+        other => panic!("unexpected error: {}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_strip_footer() {
+    let converter = StreamConverter::new();
+    let checksum = |payload: &[u8], footer: &[u8]| {
+        payload.iter().fold(0u8, |acc, b| acc.wrapping_add(*b)) == footer[0]
+    };
+
+    let body = Body::from(vec![1, 2, 3, 6]);
+    let payload = converter.body_to_bytes_strip_footer(body, 1, checksum).await.unwrap();
+    assert_eq!(payload, vec![1, 2, 3]);
+
+    let body = Body::from(vec![1, 2, 3, 7]);
+    let err = converter.body_to_bytes_strip_footer(body, 1, checksum).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::ChecksumMismatch));
+
+    let err = converter.body_to_bytes_strip_footer(Body::from(vec![1]), 4, checksum).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::FramingError(_)));
+}
+
+#[tokio::test]
+async fn test_nul_policy() {
+    use b_stb::converter::NulPolicy;
+
+    let converter = StreamConverter::new();
+    let result = converter.body_to_string(Body::from("a\0b")).await.unwrap();
+    assert_eq!(result, "a\0b");
+
+    let converter = StreamConverter::new().with_nul_policy(NulPolicy::Strip);
+    let result = converter.body_to_string(Body::from("a\0b\0")).await.unwrap();
+    assert_eq!(result, "ab");
+
+    let converter = StreamConverter::new().with_nul_policy(NulPolicy::Error);
+    let err = converter.body_to_string(Body::from("ab\0c")).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::NulByte { position: 2 }));
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_with_capacity_report() {
+    let converter = StreamConverter::new();
+    let (bytes, capacity) = converter
+        .body_to_bytes_with_capacity_report(Body::from("capacity"))
+        .await
+        .unwrap();
+    assert_eq!(bytes, b"capacity");
+    assert!(capacity >= bytes.len());
+}
+
+#[tokio::test]
+async fn test_body_to_lvt_records_across_chunks() {
+    let converter = StreamConverter::new();
+    let chunks = vec![
+        Bytes::from(vec![0, 0, 0, 3, 1, b'a']),
+        Bytes::from(vec![b'b', b'c', 0, 0]),
+        Bytes::from(vec![0, 0, 2]),
+    ];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+
+    let records = converter.body_to_lvt_records(body).await.unwrap();
+    assert_eq!(records, vec![(1, Bytes::from("abc")), (2, Bytes::new())]);
+}
+
+#[tokio::test]
+async fn test_body_to_lvt_records_truncated() {
+    let converter = StreamConverter::new();
+    let body = Body::from(vec![0, 0, 0, 5, 1, b'a']);
+
+    let err = converter.body_to_lvt_records(body).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::FramingError(_)));
+}
+
+#[tokio::test]
+async fn test_length_check_accepts_matching_length() {
+    use b_stb::converter::LengthCheck;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    let converter = StreamConverter::new().with_length_check(LengthCheck::Strict);
+    let result = converter.body_to_string(Body::from("declared")).await.unwrap();
+    assert_eq!(result, "declared");
+
+    let warned = Arc::new(AtomicBool::new(false));
+    let warned_cb = warned.clone();
+    let converter = StreamConverter::new().with_length_check(LengthCheck::warn(move |_, _| {
+        warned_cb.store(true, Ordering::SeqCst);
+    }));
+    converter.body_to_bytes(Body::from("declared")).await.unwrap();
+    assert!(!warned.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn test_body_chars_across_chunks() {
+    use futures::TryStreamExt;
+
+    let converter = StreamConverter::new();
+    let emoji = "😀".as_bytes();
+    let chunks = vec![
+        Bytes::from(vec![b'a', emoji[0], emoji[1]]),
+        Bytes::from(vec![emoji[2], emoji[3], b'b']),
+    ];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+
+    let chars: Vec<char> = converter.body_chars(body).try_collect().await.unwrap();
+    assert_eq!(chars, vec!['a', '😀', 'b']);
+}
+
+#[tokio::test]
+async fn test_body_chars_invalid_and_lossy() {
+    use futures::TryStreamExt;
+
+    let converter = StreamConverter::new();
+    let result: Result<Vec<char>, _> = converter.body_chars(Body::from(vec![b'a', 0xFF])).try_collect().await;
+    assert!(matches!(result, Err(b_stb::StreamConverterError::EncodingError(_))));
+
+    let converter = StreamConverter::new().with_lossy(true);
+    let chars: Vec<char> = converter
+        .body_chars(Body::from(vec![b'a', 0xFF, b'b', 0xE2, 0x82]))
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(chars, vec!['a', '\u{FFFD}', 'b', '\u{FFFD}']);
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_header_limited() {
+    use hyper::Response;
+
+    let converter = StreamConverter::new();
+
+    let resp = Response::builder()
+        .header("X-Max-Body", "4")
+        .body(Body::from("too long"))
+        .unwrap();
+    let err = converter.body_to_bytes_header_limited(resp, "X-Max-Body", 1024).await.unwrap_err();
+    assert!(matches!(
+        err,
+        b_stb::StreamConverterError::SizeLimitExceeded { limit: 4, read_so_far: 8 }
+    ));
+
+    let resp = Response::new(Body::from("too long"));
+    let bytes = converter.body_to_bytes_header_limited(resp, "X-Max-Body", 1024).await.unwrap();
+    assert_eq!(bytes, b"too long");
+}
+
+#[tokio::test]
+async fn test_on_error_receives_partial_bytes() {
+    use std::sync::{Arc, Mutex};
+
+    let partial = Arc::new(Mutex::new(Vec::new()));
+    let partial_cb = partial.clone();
+    let converter = StreamConverter::new().with_on_error(move |bytes, error| {
+        assert!(matches!(error, b_stb::StreamConverterError::HyperError(_)));
+        partial_cb.lock().unwrap().extend_from_slice(bytes);
+    });
+
+    let (mut sender, body) = Body::channel();
+    tokio::spawn(async move {
+        sender.send_data(Bytes::from("partial")).await.unwrap();
+        sender.abort();
+    });
+
+    let err = converter.body_to_bytes(body).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::HyperError(_)));
+    assert_eq!(&partial.lock().unwrap()[..], b"partial");
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_with_percent() {
+    use hyper::Response;
+
+    let converter = StreamConverter::new();
+    let chunks = vec![Bytes::from("ab"), Bytes::from("cd")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let resp = Response::builder().header("Content-Length", "4").body(body).unwrap();
+
+    let mut seen = Vec::new();
+    let bytes = converter
+        .body_to_bytes_with_percent(resp, |percent| seen.push(percent))
+        .await
+        .unwrap();
+    assert_eq!(bytes, b"abcd");
+    assert_eq!(seen, vec![50.0, 100.0]);
+
+    let mut calls = 0;
+    let resp = Response::new(Body::from("no length header"));
+    converter.body_to_bytes_with_percent(resp, |_| calls += 1).await.unwrap();
+    assert_eq!(calls, 0);
+}
+
+#[cfg(feature = "gzip")]
+#[tokio::test]
+async fn test_body_to_gzip_round_trip() {
+    use std::io::Read;
+
+    let converter = StreamConverter::new();
+    let original = "compress me ".repeat(100);
+
+    let compressed = converter.body_to_gzip(Body::from(original.clone()), 9).await.unwrap();
+    assert!(compressed.len() < original.len());
+
+    let mut decoded = String::new();
+    flate2::read::GzDecoder::new(&compressed[..]).read_to_string(&mut decoded).unwrap();
+    assert_eq!(decoded, original);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_min_rate_aborts_stalled_body() {
+    use std::time::Duration;
+
+    let converter = StreamConverter::new().with_min_rate(100, Duration::from_secs(1));
+    let (mut sender, body) = Body::channel();
+    tokio::spawn(async move {
+        sender.send_data(Bytes::from("slow")).await.unwrap();
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        drop(sender);
+    });
+
+    let err = converter.body_to_bytes(body).await.unwrap_err();
+    assert!(matches!(
+        err,
+        b_stb::StreamConverterError::TooSlow { min_bytes_per_sec: 100, .. }
+    ));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_min_rate_allows_fast_body() {
+    use std::time::Duration;
+
+    let converter = StreamConverter::new().with_min_rate(100, Duration::from_secs(1));
+    let result = converter.body_to_string(Body::from("fast enough")).await.unwrap();
+    assert_eq!(result, "fast enough");
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_outcome() {
+    use b_stb::converter::ReadOutcome;
+
+    let converter = StreamConverter::new();
+    let (bytes, outcome) = converter.body_to_bytes_outcome(Body::from("complete")).await;
+    assert_eq!(bytes, b"complete");
+    assert!(matches!(outcome, ReadOutcome::Complete));
+
+    let (mut sender, body) = Body::channel();
+    tokio::spawn(async move {
+        sender.send_data(Bytes::from("partial")).await.unwrap();
+        sender.abort();
+    });
+    let (bytes, outcome) = converter.body_to_bytes_outcome(body).await;
+    assert_eq!(bytes, b"partial");
+    assert!(matches!(outcome, ReadOutcome::Failed(b_stb::StreamConverterError::HyperError(_))));
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_outcome_truncates_mid_chunk() {
+    use b_stb::converter::ReadOutcome;
+
+    let converter = StreamConverter::new().with_max_size(6);
+    let chunks = vec![Bytes::from("abcd"), Bytes::from("efgh"), Bytes::from("ijkl")];
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+    let (bytes, outcome) = converter.body_to_bytes_outcome(body).await;
+    assert_eq!(bytes, b"abcdef");
+    assert!(matches!(outcome, ReadOutcome::Truncated { limit: 6 }));
+}
+
+#[tokio::test]
+async fn test_yield_every_lets_other_tasks_run() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    let ran = Arc::new(AtomicBool::new(false));
+    let ran_task = ran.clone();
+    tokio::spawn(async move { ran_task.store(true, Ordering::SeqCst) });
+
+    let ran_during = Arc::new(AtomicBool::new(false));
+    let (ran_cb, ran_during_cb) = (ran.clone(), ran_during.clone());
+    let converter = StreamConverter::new()
+        .with_yield_every(1)
+        .with_soft_limit(2, move |_| ran_during_cb.store(ran_cb.load(Ordering::SeqCst), Ordering::SeqCst));
+
+    let chunks = vec![Bytes::from("a"), Bytes::from("b"), Bytes::from("c")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+
+    let result = converter.body_to_string(body).await.unwrap();
+    assert_eq!(result, "abc");
+    assert!(ran_during.load(Ordering::SeqCst));
+}
+
+#[cfg(feature = "gzip")]
+#[tokio::test]
+async fn test_body_to_bytes_decoded_report() {
+    use hyper::Response;
+    use std::io::Write;
+
+    let converter = StreamConverter::new();
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(b"decompressed").unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    let resp = Response::builder()
+        .header("Content-Encoding", "gzip")
+        .body(Body::from(compressed))
+        .unwrap();
+    let (bytes, encoding) = converter.body_to_bytes_decoded_report(resp).await.unwrap();
+    assert_eq!(bytes, b"decompressed");
+    assert_eq!(encoding.as_deref(), Some("gzip"));
+
+    let resp = Response::builder()
+        .header("Content-Encoding", "br")
+        .body(Body::from("opaque"))
+        .unwrap();
+    let (bytes, encoding) = converter.body_to_bytes_decoded_report(resp).await.unwrap();
+    assert_eq!(bytes, b"opaque");
+    assert_eq!(encoding, None);
+
+    let resp = Response::builder()
+        .header("Content-Encoding", "gzip")
+        .body(Body::from("not gzip"))
+        .unwrap();
+    let err = converter.body_to_bytes_decoded_report(resp).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::DecompressionError(_)));
+}
+
+#[cfg(feature = "gzip")]
+#[tokio::test]
+async fn test_body_to_bytes_decoded_report_limits_decompressed_size() {
+    use hyper::Response;
+    use std::io::Write;
+
+    // 16 MiB of zeros compresses to a few KiB
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+    encoder.write_all(&vec![0u8; 16 * 1024 * 1024]).unwrap();
+    let bomb = encoder.finish().unwrap();
+    assert!(bomb.len() < 64 * 1024);
+
+    let converter = StreamConverter::new().with_max_size(64 * 1024);
+    let resp = Response::builder()
+        .header("Content-Encoding", "gzip")
+        .body(Body::from(bomb))
+        .unwrap();
+    match converter.body_to_bytes_decoded_report(resp).await {
+        Err(b_stb::StreamConverterError::SizeLimitExceeded { limit, read_so_far }) => {
+            assert_eq!(limit, 64 * 1024);
+            assert!(read_so_far > limit && read_so_far < 1024 * 1024);
+        }
+        other => panic!("expected SizeLimitExceeded, got {:?}", other.map(|(bytes, _)| bytes.len())),
+    }
+
+    // Output within the limit is unaffected
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+    encoder.write_all(&[0u8; 1024]).unwrap();
+    let resp = Response::builder()
+        .header("Content-Encoding", "gzip")
+        .body(Body::from(encoder.finish().unwrap()))
+        .unwrap();
+    let (bytes, _) = converter.body_to_bytes_decoded_report(resp).await.unwrap();
+    assert_eq!(bytes, vec![0u8; 1024]);
+}
+
+#[tokio::test]
+async fn test_body_to_single_chunk() {
+    let converter = StreamConverter::new();
+    let chunk = converter.body_to_single_chunk(Body::from("single")).await.unwrap();
+    assert_eq!(chunk, Bytes::from("single"));
+
+    let err = converter.body_to_single_chunk(Body::empty()).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::NotSingleChunk { chunks: 0 }));
+
+    let chunks = vec![Bytes::from("one"), Bytes::from("two")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let err = converter.body_to_single_chunk(body).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::NotSingleChunk { chunks: 2 }));
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_skip_header() {
+    let converter = StreamConverter::new();
+
+    // Header split across chunks
+    let chunks = vec![Bytes::from("MA"), Bytes::from("GIC1pay"), Bytes::from("load")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let payload = converter.body_to_bytes_skip_header(body, 6, Some(b"MAGIC")).await.unwrap();
+    assert_eq!(payload, b"payload");
+
+    let err = converter
+        .body_to_bytes_skip_header(Body::from("WRONG1payload"), 6, Some(b"MAGIC"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::FramingError(_)));
+
+    let err = converter
+        .body_to_bytes_skip_header(Body::from("MAG"), 6, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::FramingError(_)));
+}
+
+#[tokio::test]
+async fn test_body_to_writer_transform() {
+    use b_stb::converter::ChunkTransform;
+
+    struct Uppercase;
+
+    impl ChunkTransform for Uppercase {
+        type Error = std::io::Error;
+
+        async fn transform(&mut self, chunk: Bytes) -> Result<Bytes, Self::Error> {
+            if chunk.as_ref() == b"bad" {
+                return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "bad chunk"));
+            }
+            Ok(Bytes::from(chunk.to_ascii_uppercase()))
+        }
+
+        async fn finish(&mut self) -> Result<Bytes, Self::Error> {
+            Ok(Bytes::from_static(b"!"))
+        }
+    }
+
+    let converter = StreamConverter::new();
+    let chunks = vec![Bytes::from("hello, "), Bytes::from("world")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let mut out = Vec::new();
+    let written = converter.body_to_writer_transform(body, &mut out, Uppercase).await.unwrap();
+    assert_eq!(out, b"HELLO, WORLD!");
+    assert_eq!(written, 13);
+
+    let err = converter
+        .body_to_writer_transform(Body::from("bad"), &mut Vec::new(), Uppercase)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::TransformError(_)));
+
+    let mut small = [0u8; 4];
+    let mut writer = std::io::Cursor::new(&mut small[..]);
+    let err = converter
+        .body_to_writer_transform(Body::from("too long"), &mut writer, Uppercase)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::IoError(_)));
+}
+
+#[cfg(feature = "json")]
+#[tokio::test]
+async fn test_body_json_fields() {
+    let converter = StreamConverter::new();
+    let body = Body::from(r#"{"id": 7, "skip": {"nested": [1, 2, {"x": null}]}, "name": "demo"}"#);
+    let fields = converter.body_json_fields(body, &["id", "name", "missing"]).await.unwrap();
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields["id"], serde_json::json!(7));
+    assert_eq!(fields["name"], serde_json::json!("demo"));
+
+    let err = converter.body_json_fields(Body::from("[1, 2]"), &["id"]).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::JsonError(_)));
+
+    let err = converter.body_json_fields(Body::from(r#"{"id": 1} x"#), &["id"]).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::JsonError(_)));
+}
+
+#[cfg(feature = "json")]
+#[tokio::test]
+async fn test_body_json_fields_streams_chunks() {
+    let converter = StreamConverter::new();
+
+    // A large unwanted field split across many small chunks
+    let mut chunks = vec![Bytes::from(r#"{"id": 7, "blob": ""#)];
+    chunks.extend(std::iter::repeat_n(Bytes::from("x".repeat(64)), 1024));
+    chunks.push(Bytes::from(r#"", "name": "demo"}"#));
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+    let fields = converter.body_json_fields(body, &["id", "name"]).await.unwrap();
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields["name"], serde_json::json!("demo"));
+
+    // Malformed JSON is reported without waiting for the body to end
+    let (mut sender, body) = Body::channel();
+    let feeder = tokio::spawn(async move {
+        sender.send_data(Bytes::from(r#"{"id": oops"#)).await.unwrap();
+        std::future::pending::<()>().await;
+    });
+    let err = converter.body_json_fields(body, &["id"]).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::JsonError(_)));
+    feeder.abort();
+}
+
+#[tokio::test]
+async fn test_to_string_multibyte_across_reads() {
+    use tokio::io::AsyncReadExt;
+
+    let converter = StreamConverter::new();
+    let emoji = "😀".as_bytes();
+    // The emoji's four bytes arrive in two separate reads
+    let mut reader = (&b"hi "[..]).chain(&emoji[..2]).chain(&emoji[2..]);
+    let content = converter.to_string(&mut reader).await.unwrap();
+    assert_eq!(content, "hi 😀");
+}
+
+#[tokio::test]
+async fn test_max_size() {
+    let converter = StreamConverter::new().with_max_size(8);
+    let chunks = vec![Bytes::from("12345"), Bytes::from("67890")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let err = converter.body_to_bytes(body).await.unwrap_err();
+    assert!(matches!(
+        err,
+        b_stb::StreamConverterError::SizeLimitExceeded { limit: 8, read_so_far: 10 }
+    ));
+
+    let err = converter.body_to_string(Body::from("too long body")).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::SizeLimitExceeded { limit: 8, .. }));
+
+    let mut reader = &b"0123456789"[..];
+    let err = converter.to_bytes(&mut reader).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::SizeLimitExceeded { limit: 8, .. }));
+
+    let content = converter.body_to_string(Body::from("12345678")).await.unwrap();
+    assert_eq!(content, "12345678");
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_with_frames() {
+    let converter = StreamConverter::new();
+    let chunks = vec![Bytes::from("ab"), Bytes::from("cde"), Bytes::from("f")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let (bytes, frames) = converter.body_to_bytes_with_frames(body).await.unwrap();
+    assert_eq!(bytes, b"abcdef");
+    assert_eq!(frames, vec![2, 3, 1]);
+
+    let (bytes, frames) = converter.body_to_bytes_with_frames(Body::empty()).await.unwrap();
+    assert!(bytes.is_empty());
+    assert!(frames.is_empty());
+}
+
+#[tokio::test]
+async fn test_forward_buffer_bounds_read_ahead() {
+    use b_stb::converter::ChunkTransform;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct CheckReadAhead {
+        produced: Arc<AtomicUsize>,
+        consumed: usize,
+        max_ahead: Arc<AtomicUsize>,
+    }
+
+    impl ChunkTransform for CheckReadAhead {
+        type Error = std::io::Error;
+
+        async fn transform(&mut self, chunk: Bytes) -> Result<Bytes, Self::Error> {
+            self.consumed += 1;
+            let ahead = self.produced.load(Ordering::SeqCst) - self.consumed;
+            self.max_ahead.fetch_max(ahead, Ordering::SeqCst);
+            Ok(chunk)
+        }
+    }
+
+    for n_chunks in [1, 3] {
+        let produced = Arc::new(AtomicUsize::new(0));
+        let counter = produced.clone();
+        let body = Body::wrap_stream(futures_util::stream::iter((0..20).map(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, hyper::Error>(Bytes::from("chunk"))
+        })));
+
+        let converter = StreamConverter::new().with_forward_buffer(n_chunks);
+        let max_ahead = Arc::new(AtomicUsize::new(0));
+        let transform = CheckReadAhead { produced, consumed: 0, max_ahead: max_ahead.clone() };
+        let mut out = Vec::new();
+        let written = converter.body_to_writer_transform(body, &mut out, transform).await.unwrap();
+        assert_eq!(written, 100);
+        // The queue holds `n_chunks`, plus one chunk waiting to be sent
+        let max_ahead = max_ahead.load(Ordering::SeqCst);
+        assert!(max_ahead <= n_chunks + 1, "read {} chunks ahead", max_ahead);
+    }
+}
+
+#[tokio::test]
+async fn test_lossy_string_conversion() {
+    let converter = StreamConverter::new();
+    let content = converter.body_to_string_lossy(Body::from(vec![0xFF, 0x41])).await.unwrap();
+    assert_eq!(content, "\u{FFFD}A");
+
+    let mut reader = &[0x41, 0xFF, 0x42][..];
+    let content = converter.to_string_lossy(&mut reader).await.unwrap();
+    assert_eq!(content, "A\u{FFFD}B");
+}
+
+#[tokio::test]
+async fn test_body_to_lines() {
+    let converter = StreamConverter::new();
+    let chunks = vec![Bytes::from("first\r"), Bytes::from("\nsecond\n\nfourth\n")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let lines = converter.body_to_lines(body).await.unwrap();
+    assert_eq!(lines, vec!["first", "second", "", "fourth"]);
+
+    let lines = converter.body_to_lines(Body::from("no newline")).await.unwrap();
+    assert_eq!(lines, vec!["no newline"]);
+
+    assert!(converter.body_to_lines(Body::empty()).await.unwrap().is_empty());
+}
+
+#[cfg(feature = "compression")]
+#[tokio::test]
+async fn test_decode_body() {
+    use hyper::header::{HeaderMap, HeaderValue, CONTENT_ENCODING};
+    use std::io::Write;
+
+    let converter = StreamConverter::new();
+    let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(b"inflated").unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_ENCODING, HeaderValue::from_static("deflate"));
+    let bytes = converter.decode_body(&headers, Body::from(compressed)).await.unwrap();
+    assert_eq!(bytes, b"inflated");
+
+    let bytes = converter.decode_body(&HeaderMap::new(), Body::from("plain")).await.unwrap();
+    assert_eq!(bytes, b"plain");
+
+    headers.insert(CONTENT_ENCODING, HeaderValue::from_static("zstd"));
+    let bytes = converter.decode_body(&headers, Body::from("opaque")).await.unwrap();
+    assert_eq!(bytes, b"opaque");
+
+    headers.insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+    let err = converter.decode_body(&headers, Body::from("not gzip")).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::DecompressionError(_)));
+}
+
+#[cfg(feature = "compression")]
+#[tokio::test]
+async fn test_decode_body_limits_decompressed_size() {
+    use hyper::header::{HeaderMap, HeaderValue, CONTENT_ENCODING};
+    use std::io::Write;
+
+    let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::best());
+    encoder.write_all(&vec![b'a'; 16 * 1024 * 1024]).unwrap();
+    let bomb = encoder.finish().unwrap();
+
+    // Deliver the bomb in small chunks, as a network body would arrive
+    let chunks: Vec<Bytes> = bomb.chunks(512).map(Bytes::copy_from_slice).collect();
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+
+    let converter = StreamConverter::new().with_max_size(64 * 1024);
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_ENCODING, HeaderValue::from_static("deflate"));
+    let err = converter.decode_body(&headers, body).await.unwrap_err();
+    assert!(matches!(
+        err,
+        b_stb::StreamConverterError::SizeLimitExceeded { limit, read_so_far }
+            if limit == 64 * 1024 && read_so_far < 1024 * 1024
+    ));
+}
+
+#[cfg(feature = "compression")]
+#[tokio::test]
+async fn test_decode_body_deflate_variants() {
+    use hyper::header::{HeaderMap, HeaderValue, CONTENT_ENCODING};
+    use std::io::Write;
+
+    let converter = StreamConverter::new();
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_ENCODING, HeaderValue::from_static("deflate"));
+
+    // Raw deflate without a zlib header, as some servers send it
+    let mut encoder = flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(b"raw deflate").unwrap();
+    let raw = encoder.finish().unwrap();
+    let bytes = converter.decode_body(&headers, Body::from(raw.clone())).await.unwrap();
+    assert_eq!(bytes, b"raw deflate");
+
+    // A header split across chunks
+    let chunks: Vec<Bytes> = raw.chunks(1).map(Bytes::copy_from_slice).collect();
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+    let bytes = converter.decode_body(&headers, body).await.unwrap();
+    assert_eq!(bytes, b"raw deflate");
+
+    // Truncated streams fail instead of returning partial output
+    let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&b"truncated zlib stream ".repeat(64)).unwrap();
+    let zlib = encoder.finish().unwrap();
+    for truncated in [&zlib[..zlib.len() - 4], &zlib[..zlib.len() / 2], &raw[..raw.len() - 2]] {
+        let err = converter
+            .decode_body(&headers, Body::from(truncated.to_vec()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, b_stb::StreamConverterError::DecompressionError(_)), "{err:?}");
+    }
+
+    headers.insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(b"truncated gzip stream").unwrap();
+    let gzip = encoder.finish().unwrap();
+    let err = converter
+        .decode_body(&headers, Body::from(gzip[..gzip.len() - 4].to_vec()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::DecompressionError(_)), "{err:?}");
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_with_extension() {
+    let converter = StreamConverter::new();
+    let (bytes, extension) = converter
+        .body_to_bytes_with_extension(Body::from(&b"%PDF-1.4\n..."[..]))
+        .await
+        .unwrap();
+    assert_eq!(bytes, b"%PDF-1.4\n...");
+    assert_eq!(extension, Some("pdf"));
+
+    let (_, extension) = converter
+        .body_to_bytes_with_extension(Body::from("just text"))
+        .await
+        .unwrap();
+    assert_eq!(extension, None);
+}
+
+#[cfg(feature = "json")]
+#[tokio::test]
+async fn test_body_to_json() {
+    let converter = StreamConverter::new();
+    let values: Vec<u32> = converter.body_to_json(Body::from("[1, 2, 3]")).await.unwrap();
+    assert_eq!(values, vec![1, 2, 3]);
+
+    let err = converter.body_to_json::<Vec<u32>>(Body::from("[1, 2,")).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::JsonError(_)));
+
+    let converter = StreamConverter::new().with_max_size(4);
+    let err = converter.body_to_json::<Vec<u32>>(Body::from("[1, 2, 3]")).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::SizeLimitExceeded { limit: 4, .. }));
+}
+
+#[tokio::test]
+async fn test_body_sse_events_skips_keepalives() {
+    use futures::TryStreamExt;
+
+    let converter = StreamConverter::new();
+    let chunks = vec![
+        Bytes::from(": connected\n\nevent: update\nda"),
+        Bytes::from("ta: line one\ndata: line two\nid: 7\n\n:ping\n\n"),
+        Bytes::from("data: last\r\n\r\ndata: unterminated\n"),
+    ];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+
+    let mut keepalives = Vec::new();
+    let events: Vec<_> = converter
+        .body_sse_events_with_keepalive(body, |comment| keepalives.push(comment.to_string()))
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].event.as_deref(), Some("update"));
+    assert_eq!(events[0].data, "line one\nline two");
+    assert_eq!(events[0].id.as_deref(), Some("7"));
+    assert_eq!(events[1].event, None);
+    assert_eq!(events[1].data, "last");
+    assert_eq!(keepalives, vec![" connected", "ping"]);
+}
+
+#[tokio::test]
+async fn test_body_sse_events_line_endings() {
+    use b_stb::converter::TrailingNewline;
+    use futures::TryStreamExt;
+
+    // Lone CR endings, and a CRLF split across chunks
+    let converter = StreamConverter::new();
+    let chunks = vec![
+        Bytes::from("data: a\r\rdata: b\r"),
+        Bytes::from("\n\r"),
+        Bytes::from("\ndata: c\n\n"),
+    ];
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+    let events: Vec<_> = converter.body_sse_events(body).try_collect().await.unwrap();
+    let data: Vec<_> = events.iter().map(|event| event.data.as_str()).collect();
+    assert_eq!(data, ["a", "b", "c"]);
+
+    // An unterminated event is discarded even when body_lines would emit a final empty line
+    let converter = StreamConverter::new().with_trailing_newline(TrailingNewline::EmitEmpty);
+    let body = Body::from("data: done\n\ndata: unterminated\n");
+    let events: Vec<_> = converter.body_sse_events(body).try_collect().await.unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].data, "done");
+}
+
+#[tokio::test]
+async fn test_body_to_writer() {
+    let converter = StreamConverter::new();
+    let chunks = vec![Bytes::from("stream "), Bytes::from("to "), Bytes::from("disk")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let mut out = Vec::new();
+    let written = converter.body_to_writer(body, &mut out).await.unwrap();
+    assert_eq!(out, b"stream to disk");
+    assert_eq!(written, 14);
+
+    let mut small = [0u8; 4];
+    let mut writer = std::io::Cursor::new(&mut small[..]);
+    let err = converter.body_to_writer(Body::from("too long"), &mut writer).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::IoError(_)));
+
+    let (mut sender, body) = Body::channel();
+    sender.send_data(Bytes::from("partial")).await.unwrap();
+    sender.abort();
+    let err = converter.body_to_writer(body, &mut Vec::new()).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::HyperError(_)));
+}
+
+#[cfg(feature = "json")]
+#[tokio::test]
+async fn test_body_is_valid_json() {
+    let converter = StreamConverter::new();
+    assert!(converter.body_is_valid_json(Body::from(r#"{"a": [1, 2, {"b": null}]}"#)).await.unwrap());
+    assert!(converter.body_is_valid_json(Body::from(" 42 ")).await.unwrap());
+    assert!(!converter.body_is_valid_json(Body::from("{\"a\": }")).await.unwrap());
+    assert!(!converter.body_is_valid_json(Body::from("[1] [2]")).await.unwrap());
+    assert!(!converter.body_is_valid_json(Body::empty()).await.unwrap());
+
+    let (sender, body) = Body::channel();
+    sender.abort();
+    let err = converter.body_is_valid_json(body).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::HyperError(_)));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_timeout_covers_whole_body() {
+    use std::time::Duration;
+
+    // Each chunk arrives well within a second, but the whole body takes 5s
+    let body = Body::wrap_stream(futures_util::stream::unfold(0, |sent| async move {
+        if sent == 5 {
+            return None;
+        }
+        tokio::time::sleep(Duration::from_millis(1000)).await;
+        Some((Ok::<_, hyper::Error>(Bytes::from("tick")), sent + 1))
+    }));
+    let converter = StreamConverter::new().with_timeout(Duration::from_secs(3));
+    let err = converter.body_to_bytes(body).await.unwrap_err();
+    assert!(matches!(
+        err,
+        b_stb::StreamConverterError::Timeout { timeout } if timeout == Duration::from_secs(3)
+    ));
+
+    let bytes = converter.body_to_bytes(Body::from("fast")).await.unwrap();
+    assert_eq!(bytes, b"fast");
+}
+
+#[cfg(feature = "digest")]
+#[tokio::test]
+async fn test_body_to_bytes_with_etag() {
+    let converter = StreamConverter::new();
+    let chunks = vec![Bytes::from("a"), Bytes::from("bc")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let (bytes, etag) = converter.body_to_bytes_with_etag(body).await.unwrap();
+    assert_eq!(bytes, b"abc");
+    assert_eq!(etag, "\"ba7816bf8f01cfea414140de5dae2223\"");
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_with_hint_preallocates() {
+    let converter = StreamConverter::new();
+    let size = 5 * 1024 * 1024;
+    let chunks: Vec<Bytes> = (0..80).map(|_| Bytes::from(vec![7u8; size / 80])).collect();
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let bytes = converter.body_to_bytes_with_hint(body, size).await.unwrap();
+    assert_eq!(bytes.len(), size);
+    // A single allocation of exactly the hinted size, never regrown
+    assert_eq!(bytes.capacity(), size);
+
+    // The pre-allocation is capped at the max size
+    let converter = StreamConverter::new().with_max_size(16);
+    let bytes = converter.body_to_bytes_with_hint(Body::from("small"), 1 << 40).await.unwrap();
+    assert_eq!(bytes, b"small");
+    assert!(bytes.capacity() <= 16);
+}
+
+#[cfg(feature = "encoding")]
+#[tokio::test]
+async fn test_body_to_string_with_encoding() {
+    let converter = StreamConverter::new();
+
+    // Shift_JIS "日本" split in the middle of a two-byte character
+    let chunks = vec![Bytes::from_static(b"\x93\xfa\x96"), Bytes::from_static(b"\x7b")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let (content, had_errors) = converter
+        .body_to_string_with_encoding(body, encoding_rs::SHIFT_JIS)
+        .await
+        .unwrap();
+    assert_eq!(content, "日本");
+    assert!(!had_errors);
+
+    let (content, had_errors) = converter
+        .body_to_string_with_encoding(Body::from(vec![0x41, 0xFF]), encoding_rs::UTF_8)
+        .await
+        .unwrap();
+    assert_eq!(content, "A\u{FFFD}");
+    assert!(had_errors);
+}
+
+#[tokio::test]
+async fn test_generic_http_body() {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    struct FailingBody;
+
+    impl http_body::Body for FailingBody {
+        type Data = Bytes;
+        type Error = std::io::Error;
+
+        fn poll_data(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+            Poll::Ready(Some(Err(std::io::Error::other("boom"))))
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<Option<hyper::HeaderMap>, Self::Error>> {
+            Poll::Ready(Ok(None))
+        }
+    }
+
+    let converter = StreamConverter::new();
+    let body = http_body::Full::new(Bytes::from("full body"));
+    assert_eq!(converter.body_to_string(body).await.unwrap(), "full body");
+
+    let body = b_stb::body::MeteredBody::new(Body::from("metered"));
+    assert_eq!(converter.body_to_bytes(body).await.unwrap(), b"metered");
+
+    let bytes = process_stream(http_body::Full::new(Bytes::from("processed"))).await.unwrap();
+    assert_eq!(bytes, b"processed");
+
+    let err = converter.body_to_bytes(FailingBody).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::BodyError(_)));
+    assert_eq!(err.to_string(), "Body error: boom");
+
+    // Hyper errors keep their dedicated variant
+    let (sender, body) = Body::channel();
+    sender.abort();
+    let err = process_stream(body).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::HyperError(_)));
+}
+
+#[tokio::test]
+async fn test_body_split_once() {
+    let converter = StreamConverter::new();
+    let chunks = vec![Bytes::from("head"), Bytes::from("er\nbo"), Bytes::from("dy\nmore")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let (before, after) = converter.body_split_once(body, b'\n').await.unwrap();
+    assert_eq!(before, b"header");
+    assert_eq!(after, b"body\nmore");
+
+    let (before, after) = converter.body_split_once(Body::from("key="), b'=').await.unwrap();
+    assert_eq!(before, b"key");
+    assert!(after.is_empty());
+
+    let err = converter.body_split_once(Body::from("no delimiter"), 0).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::FramingError(_)));
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_with_progress() {
+    let converter = StreamConverter::new();
+
+    let mut reports = Vec::new();
+    let bytes = converter
+        .body_to_bytes_with_progress(Body::from("Hello"), |received, total| reports.push((received, total)))
+        .await
+        .unwrap();
+    assert_eq!(bytes, b"Hello");
+    assert_eq!(reports, vec![(5, Some(5))]);
+
+    let chunks = vec![Bytes::from("ab"), Bytes::from("cde")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let mut reports = Vec::new();
+    converter
+        .body_to_bytes_with_progress(body, |received, total| reports.push((received, total)))
+        .await
+        .unwrap();
+    assert_eq!(reports, vec![(2, None), (5, None)]);
+}
+
+#[cfg(feature = "encoding")]
+#[tokio::test]
+async fn test_body_to_string_with_charset() {
+    let converter = StreamConverter::new();
+    // "Grüße €" in windows-1252
+    let latin = vec![0x47, 0x72, 0xFC, 0xDF, 0x65, 0x20, 0x80];
+
+    let content = converter
+        .body_to_string_with_charset(Some("text/html; Charset=\"Windows-1252\""), Body::from(latin.clone()))
+        .await
+        .unwrap();
+    assert_eq!(content, "Grüße €");
+
+    // Without a charset the body is decoded as UTF-8, replacing invalid bytes
+    let content = converter
+        .body_to_string_with_charset(Some("text/html"), Body::from(latin))
+        .await
+        .unwrap();
+    assert_eq!(content, "Gr\u{FFFD}\u{FFFD}e \u{FFFD}");
+
+    let content = converter
+        .body_to_string_with_charset(Some("text/plain; charset=bogus"), Body::from("plain"))
+        .await
+        .unwrap();
+    assert_eq!(content, "plain");
+}
+
+#[tokio::test]
+async fn test_body_to_cursor() {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let converter = StreamConverter::new();
+    let chunks = vec![Bytes::from("0123"), Bytes::from("456789")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let mut cursor = converter.body_to_cursor(body).await.unwrap();
+    assert_eq!(cursor.position(), 0);
+
+    cursor.seek(SeekFrom::End(-3)).unwrap();
+    let mut tail = Vec::new();
+    cursor.read_to_end(&mut tail).unwrap();
+    assert_eq!(tail, b"789");
+
+    cursor.seek(SeekFrom::Start(2)).unwrap();
+    let mut two = [0u8; 2];
+    cursor.read_exact(&mut two).unwrap();
+    assert_eq!(&two, b"23");
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_zerocopy() {
+    let converter = StreamConverter::new();
+
+    let original = Bytes::from(vec![1u8; 1024]);
+    let bytes = converter.body_to_bytes_zerocopy(Body::from(original.clone())).await.unwrap();
+    // A single chunk is handed back without copying
+    assert_eq!(bytes.as_ptr(), original.as_ptr());
+
+    let chunks = vec![Bytes::from("a"), Bytes::from("b"), Bytes::from("c")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    assert_eq!(converter.body_to_bytes_zerocopy(body).await.unwrap(), Bytes::from("abc"));
+    assert!(converter.body_to_bytes_zerocopy(Body::empty()).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_body_to_string_lossy_max_replacements() {
+    let converter = StreamConverter::new();
+    let corrupted = vec![0xFF, b'a', 0xC3, b'b', 0xFE];
+
+    let content = converter
+        .body_to_string_lossy_max_replacements(Body::from(corrupted.clone()), 3)
+        .await
+        .unwrap();
+    assert_eq!(content, "\u{FFFD}a\u{FFFD}b\u{FFFD}");
+
+    let err = converter
+        .body_to_string_lossy_max_replacements(Body::from(corrupted), 2)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::TooManyReplacements { max: 2 }));
+
+    let content = converter
+        .body_to_string_lossy_max_replacements(Body::from("clean ✓"), 0)
+        .await
+        .unwrap();
+    assert_eq!(content, "clean ✓");
+}
+
+#[tokio::test]
+async fn test_body_to_string_lossy_max_replacements_applies_bom_and_nul_policy() {
+    use b_stb::converter::NulPolicy;
+
+    let converter = StreamConverter::new().with_strip_bom(true);
+    let content = converter
+        .body_to_string_lossy_max_replacements(Body::from(&b"\xEF\xBB\xBFhi \xFF"[..]), 1)
+        .await
+        .unwrap();
+    assert_eq!(content, "hi \u{FFFD}");
+
+    let converter = StreamConverter::new().with_nul_policy(NulPolicy::Strip);
+    let content = converter
+        .body_to_string_lossy_max_replacements(Body::from(&b"a\0b"[..]), 0)
+        .await
+        .unwrap();
+    assert_eq!(content, "ab");
+
+    let converter = StreamConverter::new().with_nul_policy(NulPolicy::Error);
+    let err = converter
+        .body_to_string_lossy_max_replacements(Body::from(&b"a\0b"[..]), 0)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::NulByte { position: 1 }));
+}
+
+#[tokio::test]
+async fn test_process_body_lines() {
+    use futures::TryStreamExt;
+
+    let chunks = vec![Bytes::from("{\"id\": 1}\r\n{\"id\""), Bytes::from(": 2}\n\npartial")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    let lines: Vec<String> = b_stb::process::body_lines(body).try_collect().await.unwrap();
+    assert_eq!(lines, vec!["{\"id\": 1}", "{\"id\": 2}", "", "partial"]);
+
+    let lines: Vec<String> = b_stb::process::body_lines(Body::from("a\n")).try_collect().await.unwrap();
+    assert_eq!(lines, vec!["a"]);
+}
+
+#[tokio::test]
+async fn test_body_lines_long_line_in_small_chunks() {
+    use futures::TryStreamExt;
+
+    // A long line and a CRLF, both split across many tiny chunks
+    let data = format!("{}\r\nshort\nend", "x".repeat(256 * 1024));
+    let chunks: Vec<Bytes> = data.as_bytes().chunks(7).map(Bytes::copy_from_slice).collect();
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+
+    let lines: Vec<String> = b_stb::process::body_lines(body).try_collect().await.unwrap();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0].len(), 256 * 1024);
+    assert_eq!(&lines[1..], ["short", "end"]);
+}
+
+#[tokio::test]
+async fn test_streaming_response_headers_before_body() {
+    use futures::StreamExt;
+    use hyper::{Response, StatusCode};
+
+    let converter = StreamConverter::new();
+    let (mut sender, body) = Body::channel();
+    let resp = Response::builder()
+        .status(StatusCode::ACCEPTED)
+        .header("X-Trace", "abc")
+        .body(body)
+        .unwrap();
+
+    // Headers are available while no body data has been sent yet
+    let streaming = converter.streaming_response(resp);
+    assert_eq!(streaming.status(), StatusCode::ACCEPTED);
+    assert_eq!(streaming.headers()["X-Trace"], "abc");
+
+    let mut chunks = Box::pin(streaming.body_stream());
+    sender.send_data(Bytes::from("first")).await.unwrap();
+    assert_eq!(chunks.next().await.unwrap().unwrap(), Bytes::from("first"));
+
+    sender.abort();
+    let err = chunks.next().await.unwrap().unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::HyperError(_)));
+}
+
+#[tokio::test]
+async fn test_error_from_conversions() {
+    use b_stb::StreamConverterError;
+    use tokio::io::AsyncReadExt;
+
+    async fn read_text(mut reader: &[u8]) -> Result<String, StreamConverterError> {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await?;
+        Ok(String::from_utf8(buf)?)
+    }
+
+    assert_eq!(read_text(b"ok").await.unwrap(), "ok");
+    assert!(matches!(read_text(&[0xFF]).await, Err(StreamConverterError::EncodingError(_))));
+
+    let err: StreamConverterError = std::io::Error::other("disk").into();
+    assert!(matches!(err, StreamConverterError::IoError(_)));
+
+    let (sender, mut body) = Body::channel();
+    sender.abort();
+    let hyper_err = futures::StreamExt::next(&mut body).await.unwrap().unwrap_err();
+    let err: StreamConverterError = hyper_err.into();
+    assert!(matches!(err, StreamConverterError::HyperError(_)));
+}
+
+#[tokio::test]
+async fn test_body_find() {
+    let converter = StreamConverter::new();
+
+    // The needle spans three chunks
+    let chunks = vec![Bytes::from("xxxxBO"), Bytes::from("U"), Bytes::from("NDARYyyyy")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    assert_eq!(converter.body_find(body, b"BOUNDARY").await.unwrap(), Some(4));
+
+    let chunks = vec![Bytes::from("abc"), Bytes::from("abd"), Bytes::from("abe")];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    assert_eq!(converter.body_find(body, b"dab").await.unwrap(), Some(5));
+
+    assert_eq!(converter.body_find(Body::from("haystack"), b"needle").await.unwrap(), None);
+    assert_eq!(converter.body_find(Body::from("abc"), b"").await.unwrap(), Some(0));
+}
+
+#[tokio::test]
+async fn test_strip_bom() {
+    let converter = StreamConverter::new().with_strip_bom(true);
+
+    let mut bom = vec![0xEF, 0xBB, 0xBF];
+    bom.extend_from_slice(b"hi");
+    assert_eq!(converter.body_to_string(Body::from(bom.clone())).await.unwrap(), "hi");
+
+    // BOM split across chunks
+    let chunks = vec![Bytes::from_static(&[0xEF, 0xBB]), Bytes::from_static(&[0xBF, b'h', b'i'])];
+    let body = Body::wrap_stream(futures_util::stream::iter(
+        chunks.into_iter().map(Ok::<_, hyper::Error>)
+    ));
+    assert_eq!(converter.body_to_string(body).await.unwrap(), "hi");
+
+    let mut reader = &bom[..];
+    assert_eq!(converter.to_string(&mut reader).await.unwrap(), "hi");
+
+    // Only a leading BOM is removed
+    let content = converter.body_to_string(Body::from("a\u{FEFF}b")).await.unwrap();
+    assert_eq!(content, "a\u{FEFF}b");
+
+    // Disabled by default
+    let content = StreamConverter::new().body_to_string(Body::from(bom)).await.unwrap();
+    assert_eq!(content, "\u{FEFF}hi");
+}
+
+#[tokio::test]
+async fn test_stream_to_bytes() {
+    let converter = StreamConverter::new();
+    let chunks = vec![Ok::<_, std::io::Error>(Bytes::from("from ")), Ok(Bytes::from("a stream"))];
+    let bytes = converter.stream_to_bytes(futures_util::stream::iter(chunks)).await.unwrap();
+    assert_eq!(bytes, b"from a stream");
+
+    let chunks = vec![Ok(Bytes::from("partial")), Err(std::io::Error::other("sdk failure"))];
+    let err = converter.stream_to_bytes(futures_util::stream::iter(chunks)).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::BodyError(_)));
+    assert_eq!(err.to_string(), "Body error: sdk failure");
+
+    let converter = StreamConverter::new().with_max_size(4);
+    let chunks = vec![Ok::<_, std::io::Error>(Bytes::from("too long"))];
+    let err = converter.stream_to_bytes(futures_util::stream::iter(chunks)).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::SizeLimitExceeded { limit: 4, .. }));
+}
+
+#[tokio::test]
+async fn test_body_to_printable_string() {
+    let converter = StreamConverter::new();
+    let content = converter
+        .body_to_printable_string(Body::from("col1\tcol2\nnext line"))
+        .await
+        .unwrap();
+    assert_eq!(content, "col1\tcol2\nnext line");
+
+    let err = converter
+        .body_to_printable_string(Body::from("ok\u{1b}[2Jcleared"))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        b_stb::StreamConverterError::NonPrintable { codepoint: '\u{1b}', position: 2 }
+    ));
+
+    let err = converter
+        .body_to_printable_string(Body::from("é\rforged"))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        b_stb::StreamConverterError::NonPrintable { codepoint: '\r', position: 2 }
+    ));
+}
+
+#[cfg(feature = "gzip")]
+#[tokio::test]
+async fn test_body_to_bytes_with_compressed_size() {
+    let converter = StreamConverter::new();
+    let original = "compress me ".repeat(100);
+    let chunks = vec![Bytes::from(original[..600].to_string()), Bytes::from(original[600..].to_string())];
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+
+    let (bytes, compressed_size) = converter.body_to_bytes_with_compressed_size(body).await.unwrap();
+    assert_eq!(bytes, original.as_bytes());
+
+    let compressed = converter.body_to_gzip(Body::from(original.clone()), 6).await.unwrap();
+    assert_eq!(compressed_size, compressed.len());
+    assert!(compressed_size < bytes.len());
+}
+
+#[tokio::test]
+async fn test_body_to_strings_concurrent() {
+    let converter = StreamConverter::new().with_max_size(8);
+    let (sender, failing) = Body::channel();
+    sender.abort();
+    let bodies = vec![
+        Body::from("first"),
+        failing,
+        Body::from("much too long"),
+        Body::from("last"),
+    ];
+
+    let results = converter.body_to_strings_concurrent(bodies, 2).await;
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0].as_deref().unwrap(), "first");
+    assert!(matches!(results[1], Err(b_stb::StreamConverterError::HyperError(_))));
+    assert!(matches!(
+        results[2],
+        Err(b_stb::StreamConverterError::SizeLimitExceeded { limit: 8, .. })
+    ));
+    assert_eq!(results[3].as_deref().unwrap(), "last");
+
+    let results = converter.body_to_strings_concurrent(vec![Body::from("ok")], 0).await;
+    assert_eq!(results[0].as_deref().unwrap(), "ok");
+}
+
+/// An `AsyncRead` that returns at most `max_read` bytes per read and records
+/// the size of every buffer it is handed.
+struct RecordingReader {
+    data: Vec<u8>,
+    pos: usize,
+    max_read: usize,
+    buffer_sizes: Vec<usize>,
+}
+
+impl tokio::io::AsyncRead for RecordingReader {
+    fn poll_read(
+        mut self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        self.buffer_sizes.push(buf.remaining());
+        let len = buf.remaining().min(self.max_read).min(self.data.len() - self.pos);
+        buf.put_slice(&self.data[self.pos..self.pos + len]);
+        self.pos += len;
+        std::task::Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn test_adaptive_buffer() {
+    let data = vec![7u8; 256 * 1024];
+
+    // Fixed buffer by default
+    let mut reader = RecordingReader { data: data.clone(), pos: 0, max_read: usize::MAX, buffer_sizes: Vec::new() };
+    let bytes = StreamConverter::with_buffer_size(1024).to_bytes(&mut reader).await.unwrap();
+    assert_eq!(bytes, data);
+    assert!(reader.buffer_sizes.iter().all(|&size| size == 1024));
+
+    // Grows when reads fill the buffer
+    let converter = StreamConverter::with_buffer_size(1024).with_adaptive_buffer(true);
+    let mut reader = RecordingReader { data: data.clone(), pos: 0, max_read: usize::MAX, buffer_sizes: Vec::new() };
+    let bytes = converter.to_bytes(&mut reader).await.unwrap();
+    assert_eq!(bytes, data);
+    assert_eq!(reader.buffer_sizes[0], 1024);
+    assert!(reader.buffer_sizes.iter().any(|&size| size >= 16 * 1024));
+    assert!(reader.buffer_sizes.windows(2).all(|w| w[1] >= w[0]));
+
+    // Shrinks, but not below the minimum, when reads underfill it
+    let converter = StreamConverter::with_buffer_size(64 * 1024).with_adaptive_buffer(true);
+    let mut reader = RecordingReader { data: vec![1u8; 4096], pos: 0, max_read: 16, buffer_sizes: Vec::new() };
+    let bytes = converter.to_bytes(&mut reader).await.unwrap();
+    assert_eq!(bytes.len(), 4096);
+    assert_eq!(reader.buffer_sizes[0], 64 * 1024);
+    assert_eq!(*reader.buffer_sizes.last().unwrap(), 1024);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_builder() {
+    let converter = b_stb::StreamConverterBuilder::new()
+        .buffer_size(4)
+        .max_size(16)
+        .strip_bom(true)
+        .lossy(true)
+        .timeout(std::time::Duration::from_secs(1))
+        .build();
+
+    let content = converter.body_to_string(Body::from(b"\xEF\xBB\xBFok\xFF".to_vec())).await.unwrap();
+    assert_eq!(content, "ok\u{FFFD}");
+
+    let err = converter.body_to_bytes(Body::from("x".repeat(17))).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::SizeLimitExceeded { limit: 16, .. }));
+
+    let (_sender, stalled) = Body::channel();
+    let err = converter.body_to_bytes(stalled).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::Timeout { .. }));
+
+    let mut reader: &[u8] = b"four byte reads";
+    let bytes = converter.to_bytes(&mut reader).await.unwrap();
+    assert_eq!(bytes, b"four byte reads".to_vec());
+
+    let converter = StreamConverter::builder().build();
+    assert_eq!(converter.body_to_string(Body::from("defaults")).await.unwrap(), "defaults");
+}
+
+#[cfg(feature = "tempfile")]
+#[tokio::test]
+async fn test_body_to_buf_reader() {
+    use tokio::io::AsyncBufReadExt;
+
+    let converter = StreamConverter::with_buffer_size(16);
+    let chunks = vec![Bytes::from("first li"), Bytes::from("ne\nsecond line\nthird")];
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+
+    let reader = converter.body_to_buf_reader(body).await.unwrap();
+    let mut lines = reader.lines();
+    let mut collected = Vec::new();
+    while let Some(line) = lines.next_line().await.unwrap() {
+        collected.push(line);
+    }
+    assert_eq!(collected, vec!["first line", "second line", "third"]);
+
+    let err = StreamConverter::new()
+        .with_max_size(4)
+        .body_to_buf_reader(Body::from("too large"))
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(err, b_stb::StreamConverterError::SizeLimitExceeded { limit: 4, .. }));
+}
+
+#[tokio::test]
+async fn test_zero_buffer_size() {
+    let err = StreamConverter::try_with_buffer_size(0).unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::InvalidBufferSize { size: 0 }));
+
+    let data = b"a non-empty reader must not be truncated";
+    for converter in [
+        StreamConverter::with_buffer_size(0),
+        StreamConverter::try_with_buffer_size(1).unwrap(),
+    ] {
+        let mut reader: &[u8] = data;
+        let bytes = converter.to_bytes(&mut reader).await.unwrap();
+        assert_eq!(bytes, data.to_vec());
+    }
+}
+
+#[cfg(feature = "json")]
+#[tokio::test]
+async fn test_body_to_json_head() {
+    let converter = StreamConverter::new();
+    let chunks = vec![Bytes::from("{\"id\": 7, \"tags\": [\"a\"]"), Bytes::from("}\n{\"id\": 8}trailer")];
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+
+    let (value, rest): (serde_json::Value, _) = converter.body_to_json_head(body).await.unwrap();
+    assert_eq!(value["id"], 7);
+    assert_eq!(rest, b"\n{\"id\": 8}trailer");
+
+    let (value, rest): (Vec<u8>, _) = converter.body_to_json_head(Body::from("[1,2]")).await.unwrap();
+    assert_eq!(value, vec![1, 2]);
+    assert!(rest.is_empty());
+
+    let err = converter.body_to_json_head::<serde_json::Value>(Body::from("  ")).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::JsonError(_)));
+
+    let err = converter.body_to_json_head::<serde_json::Value>(Body::from("{\"open\": ")).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::JsonError(_)));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_body_to_bytes_with_ttfb() {
+    use std::time::Duration;
+
+    let converter = StreamConverter::new();
+    let (mut sender, body) = Body::channel();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        sender.send_data(Bytes::from("first")).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        sender.send_data(Bytes::from(" last")).await.unwrap();
+    });
+
+    let (bytes, ttfb, total) = converter.body_to_bytes_with_ttfb(body).await.unwrap();
+    assert_eq!(bytes, b"first last");
+    assert!(ttfb >= Duration::from_millis(200) && ttfb < Duration::from_millis(300));
+    assert!(total >= Duration::from_millis(500));
+
+    let (bytes, ttfb, total) = converter.body_to_bytes_with_ttfb(Body::empty()).await.unwrap();
+    assert!(bytes.is_empty());
+    assert_eq!(ttfb, total);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_body_to_bytes_retry() {
+    let converter = StreamConverter::new();
+
+    // Two transient failures, then success
+    let mut attempts = 0;
+    let bytes = converter
+        .body_to_bytes_retry(
+            || {
+                attempts += 1;
+                let fail = attempts < 3;
+                async move {
+                    if fail {
+                        let (sender, body) = Body::channel();
+                        sender.abort();
+                        Ok::<_, hyper::Error>(body)
+                    } else {
+                        Ok(Body::from("recovered"))
+                    }
+                }
+            },
+            2,
+        )
+        .await
+        .unwrap();
+    assert_eq!(bytes, b"recovered");
+    assert_eq!(attempts, 3);
+
+    // Retries exhausted: the last transient error is returned
+    let mut attempts = 0;
+    let err = converter
+        .body_to_bytes_retry(
+            || {
+                attempts += 1;
+                async {
+                    let (sender, body) = Body::channel();
+                    sender.abort();
+                    Ok::<_, hyper::Error>(body)
+                }
+            },
+            1,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::HyperError(_)));
+    assert_eq!(attempts, 2);
+
+    // Non-transient errors are not retried
+    let converter = StreamConverter::new().with_max_size(2);
+    let mut attempts = 0;
+    let err = converter
+        .body_to_bytes_retry(
+            || {
+                attempts += 1;
+                async { Ok::<_, hyper::Error>(Body::from("too big")) }
+            },
+            5,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::SizeLimitExceeded { .. }));
+    assert_eq!(attempts, 1);
+
+    // Failures to obtain a body are retried too, rather than read as an empty body
+    let converter = StreamConverter::new();
+    let mut attempts = 0;
+    let bytes = converter
+        .body_to_bytes_retry(
+            || {
+                attempts += 1;
+                let fail = attempts < 2;
+                async move {
+                    if fail {
+                        Err(std::io::Error::from(std::io::ErrorKind::ConnectionRefused))
+                    } else {
+                        Ok(Body::from("connected"))
+                    }
+                }
+            },
+            1,
+        )
+        .await
+        .unwrap();
+    assert_eq!(bytes, b"connected");
+    assert_eq!(attempts, 2);
+
+    let err = converter
+        .body_to_bytes_retry(
+            || async { Err::<Body, _>(std::io::Error::from(std::io::ErrorKind::ConnectionRefused)) },
+            1,
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::IoError(_)));
+
+    // I/O errors that refetching cannot fix are not retried
+    for kind in [std::io::ErrorKind::InvalidData, std::io::ErrorKind::Other] {
+        let mut attempts = 0;
+        let err = converter
+            .body_to_bytes_retry(
+                || {
+                    attempts += 1;
+                    async move { Err::<Body, _>(std::io::Error::from(kind)) }
+                },
+                3,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, b_stb::StreamConverterError::IoError(_)));
+        assert_eq!(attempts, 1);
+    }
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_transform_prefix() {
+    let converter = StreamConverter::new();
+    let chunks = vec![Bytes::from("ab"), Bytes::from("cd"), Bytes::from("efgh")];
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+
+    let mut calls = 0;
+    let bytes = converter
+        .body_to_bytes_transform_prefix(body, 5, |prefix| {
+            calls += 1;
+            assert_eq!(prefix, b"abcde");
+            prefix.make_ascii_uppercase();
+        })
+        .await
+        .unwrap();
+    assert_eq!(bytes, b"ABCDEfgh");
+    assert_eq!(calls, 1);
+
+    let bytes = converter
+        .body_to_bytes_transform_prefix(Body::from("xy"), 10, |prefix| prefix.reverse())
+        .await
+        .unwrap();
+    assert_eq!(bytes, b"yx");
+}
+
+#[tokio::test]
+async fn test_clone_shares_configuration() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let crossed = Arc::new(AtomicUsize::new(0));
+    let counter = crossed.clone();
+    let converter = StreamConverter::with_buffer_size(4096)
+        .with_max_size(8)
+        .with_soft_limit(2, move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+    assert_eq!(converter.buffer_size(), 4096);
+
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let converter = converter.clone();
+            tokio::spawn(async move { converter.body_to_bytes(Body::from("four")).await })
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.await.unwrap().unwrap(), b"four");
+    }
+    assert_eq!(crossed.load(Ordering::SeqCst), 2);
+
+    let clone = converter.clone();
+    assert_eq!(clone.buffer_size(), 4096);
+    assert!(clone.body_to_bytes(Body::from("too long!")).await.is_err());
+}
+
+#[tokio::test]
+async fn test_with_concurrency_limit() {
+    use std::sync::Arc;
+    use tokio::sync::Semaphore;
+
+    let semaphore = Arc::new(Semaphore::new(1));
+    let converter = StreamConverter::new().with_concurrency_limit(semaphore.clone());
+
+    // The first read holds the only permit until its body ends
+    let (mut sender, body) = Body::channel();
+    let first = tokio::spawn({
+        let converter = converter.clone();
+        async move { converter.body_to_bytes(body).await }
+    });
+    while semaphore.available_permits() > 0 {
+        tokio::task::yield_now().await;
+    }
+
+    let second = tokio::spawn({
+        let converter = converter.clone();
+        async move { converter.body_to_bytes(Body::from("second")).await }
+    });
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+    assert!(!second.is_finished());
+
+    sender.send_data(Bytes::from("first")).await.unwrap();
+    drop(sender);
+    assert_eq!(first.await.unwrap().unwrap(), b"first");
+    assert_eq!(second.await.unwrap().unwrap(), b"second");
+    assert_eq!(semaphore.available_permits(), 1);
+
+    semaphore.close();
+    let err = converter.body_to_bytes(Body::from("closed")).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::IoError(_)));
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_chunk_timeout_jitter() {
+    use std::time::Duration;
+
+    let converter = StreamConverter::new().with_chunk_timeout_jitter(Duration::from_secs(1), 2);
+
+    // A third slow chunk is one stall too many
+    let (mut sender, body) = Body::channel();
+    tokio::spawn(async move {
+        for chunk in ["a", "b", "c"] {
+            tokio::time::sleep(Duration::from_millis(1500)).await;
+            sender.send_data(Bytes::from(chunk)).await.unwrap();
+        }
+    });
+    let err = converter.body_to_bytes(body).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::Timeout { timeout } if timeout == Duration::from_secs(1)));
+
+    // Two hiccups among fast chunks are tolerated
+    let (mut sender, body) = Body::channel();
+    tokio::spawn(async move {
+        for (i, chunk) in ["a", "b", "c", "d"].into_iter().enumerate() {
+            let delay = if i % 2 == 0 { 1500 } else { 100 };
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+            sender.send_data(Bytes::from(chunk)).await.unwrap();
+        }
+    });
+    assert_eq!(converter.body_to_bytes(body).await.unwrap(), b"abcd");
+
+    // A dead connection fails after the allowed stalls are used up
+    let (_sender, body) = Body::channel();
+    let started = tokio::time::Instant::now();
+    let err = converter.body_to_bytes(body).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::Timeout { .. }));
+    assert_eq!(started.elapsed(), Duration::from_secs(3));
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_partial() {
+    let converter = StreamConverter::new();
+    let (mut sender, body) = Body::channel();
+    tokio::spawn(async move {
+        sender.send_data(Bytes::from("<html>upstream ")).await.unwrap();
+        sender.send_data(Bytes::from("error")).await.unwrap();
+        sender.abort();
+    });
+
+    let (bytes, error) = converter.body_to_bytes_partial(body).await;
+    assert_eq!(bytes, b"<html>upstream error");
+    assert!(matches!(error, Some(b_stb::StreamConverterError::HyperError(_))));
+
+    let (bytes, error) = converter.body_to_bytes_partial(Body::from("complete")).await;
+    assert_eq!(bytes, b"complete");
+    assert!(error.is_none());
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_borrowed() {
+    use b_stb::converter::GuardedBody;
+    use std::time::Duration;
+
+    let converter = StreamConverter::new();
+    let mut body = GuardedBody::new(Body::from("data"));
+    assert_eq!(converter.body_to_bytes_borrowed(&mut body).await.unwrap(), b"data");
+    assert_eq!(converter.body_to_bytes_borrowed(&mut body).await.unwrap(), b"");
+
+    // A cancelled read releases the body for the next one
+    let (mut sender, body) = Body::channel();
+    let mut body = GuardedBody::new(body);
+    let cancelled = tokio::time::timeout(Duration::from_millis(10), converter.body_to_bytes_borrowed(&mut body)).await;
+    assert!(cancelled.is_err());
+    tokio::spawn(async move {
+        sender.send_data(Bytes::from("rest")).await.unwrap();
+    });
+    assert_eq!(converter.body_to_bytes_borrowed(&mut body).await.unwrap(), b"rest");
+
+    let mut reader = GuardedBody::new(&b"\xEF\xBB\xBFtext"[..]);
+    let converter = StreamConverter::new().with_strip_bom(true);
+    assert_eq!(converter.to_string_borrowed(&mut reader).await.unwrap(), "text");
+    assert_eq!(converter.to_bytes_borrowed(&mut reader).await.unwrap(), b"");
+}
+
+#[cfg(debug_assertions)]
+#[tokio::test]
+#[should_panic(expected = "overlapping reads on the same body")]
+async fn test_body_to_bytes_borrowed_detects_leaked_reads() {
+    use b_stb::converter::GuardedBody;
+
+    let converter = StreamConverter::new();
+    let (_sender, body) = Body::channel();
+    let mut body = GuardedBody::new(body);
+
+    // Leak an in-flight read, leaving the body partly consumed
+    let mut first = Box::pin(converter.body_to_bytes_borrowed(&mut body));
+    assert!(futures::poll!(first.as_mut()).is_pending());
+    std::mem::forget(first);
+
+    let _ = converter.body_to_bytes_borrowed(&mut body).await;
+}
+
+#[cfg(debug_assertions)]
+#[tokio::test]
+#[should_panic(expected = "overlapping reads on the same body")]
+async fn test_to_bytes_borrowed_detects_leaked_reads() {
+    use b_stb::converter::GuardedBody;
+
+    let converter = StreamConverter::new();
+    let (_writer, reader) = tokio::io::duplex(64);
+    let mut reader = GuardedBody::new(reader);
+
+    let mut first = Box::pin(converter.to_bytes_borrowed(&mut reader));
+    assert!(futures::poll!(first.as_mut()).is_pending());
+    std::mem::forget(first);
+
+    let _ = converter.to_string_lossy_borrowed(&mut reader).await;
+}
+
+#[tokio::test]
+async fn test_guarded_bodies_do_not_share_flags() {
+    use b_stb::converter::GuardedBody;
+
+    // A leaked read poisons only its own wrapper
+    let converter = StreamConverter::new();
+    let (_sender, body) = Body::channel();
+    let mut leaked = GuardedBody::new(body);
+    let mut first = Box::pin(converter.body_to_bytes_borrowed(&mut leaked));
+    assert!(futures::poll!(first.as_mut()).is_pending());
+    std::mem::forget(first);
+
+    for _ in 0..3 {
+        let mut body = GuardedBody::new(Body::from("fresh"));
+        assert_eq!(converter.body_to_bytes_borrowed(&mut body).await.unwrap(), b"fresh");
+    }
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_clean() {
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    let converter = StreamConverter::new();
+    let (bytes, clean) = converter.body_to_bytes_clean(Body::from("complete")).await.unwrap();
+    assert_eq!(bytes, b"complete");
+    assert!(clean);
+
+    // A server that resets the connection partway through the body
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = [0u8; 1024];
+        let _ = socket.read(&mut request).await.unwrap();
+        socket
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\npartial")
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        socket.set_zero_linger().unwrap();
+    });
+
+    let uri = format!("http://{}/", addr).parse().unwrap();
+    let response = hyper::Client::new().get(uri).await.unwrap();
+    let (bytes, clean) = converter.body_to_bytes_clean(response.into_body()).await.unwrap();
+    assert_eq!(bytes, b"partial");
+    assert!(!clean);
+
+    // Errors other than a reset are still reported
+    let (sender, body) = Body::channel();
+    sender.abort();
+    let err = converter.body_to_bytes_clean(body).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::HyperError(_)));
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_with_nul_positions() {
+    let converter = StreamConverter::new().with_nul_policy(b_stb::converter::NulPolicy::Error);
+    let chunks = vec![Bytes::from(&b"\0ab"[..]), Bytes::from(&b"c\0"[..]), Bytes::from(&b"d\0"[..])];
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+
+    let (bytes, positions) = converter.body_to_bytes_with_nul_positions(body).await.unwrap();
+    assert_eq!(bytes, b"\0abc\0d\0");
+    assert_eq!(positions, vec![0, 4, 6]);
+
+    let (_, positions) = converter.body_to_bytes_with_nul_positions(Body::from("clean")).await.unwrap();
+    assert!(positions.is_empty());
+}
+
+#[cfg(feature = "reqwest")]
+#[tokio::test]
+async fn test_reqwest_response() {
+    let converter = StreamConverter::new();
+    let chunks = vec![Ok::<_, std::io::Error>(Bytes::from("hello ")), Ok(Bytes::from("reqwest"))];
+    let body = reqwest::Body::wrap_stream(futures_util::stream::iter(chunks));
+    let resp = reqwest::Response::from(http1::Response::new(body));
+    assert_eq!(converter.response_to_string(resp).await.unwrap(), "hello reqwest");
+
+    let resp = reqwest::Response::from(http1::Response::new(reqwest::Body::from(vec![0xFFu8, 0x00])));
+    assert_eq!(converter.response_to_bytes(resp).await.unwrap(), vec![0xFF, 0x00]);
+
+    let chunks = vec![Ok(Bytes::from("partial")), Err(std::io::Error::other("reset"))];
+    let body = reqwest::Body::wrap_stream(futures_util::stream::iter(chunks));
+    let resp = reqwest::Response::from(http1::Response::new(body));
+    let err = converter.response_to_bytes(resp).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::ReqwestError(_)));
+}
+
+#[cfg(feature = "reqwest")]
+#[tokio::test]
+async fn test_reqwest_response_length_check() {
+    use b_stb::converter::LengthCheck;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    /// A body that declares more bytes than it delivers.
+    struct ShortBody(Option<Bytes>);
+
+    impl http_body1::Body for ShortBody {
+        type Data = Bytes;
+        type Error = std::io::Error;
+
+        fn poll_frame(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<http_body1::Frame<Bytes>, std::io::Error>>> {
+            Poll::Ready(self.0.take().map(|data| Ok(http_body1::Frame::data(data))))
+        }
+
+        fn size_hint(&self) -> http_body1::SizeHint {
+            http_body1::SizeHint::with_exact(10)
+        }
+    }
+
+    let converter = StreamConverter::new().with_length_check(LengthCheck::Strict);
+    let resp = reqwest::Response::from(http1::Response::new(reqwest::Body::from("declared")));
+    assert_eq!(converter.response_to_string(resp).await.unwrap(), "declared");
+
+    let body = reqwest::Body::wrap(ShortBody(Some(Bytes::from("short"))));
+    let resp = reqwest::Response::from(http1::Response::new(body));
+    assert_eq!(resp.content_length(), Some(10));
+    let err = converter.response_to_bytes(resp).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::UnexpectedEof { expected: 10, received: 5 }));
+}
+
+#[tokio::test]
+async fn test_body_to_stdout() {
+    let converter = StreamConverter::new();
+    let chunks = vec![Bytes::from("line one\nline "), Bytes::from("two\n")];
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+    assert_eq!(converter.body_to_stdout(body, true).await.unwrap(), 18);
+
+    assert_eq!(converter.body_to_stdout(Body::from("chunked\n"), false).await.unwrap(), 8);
+    assert_eq!(converter.body_to_stdout(Body::empty(), true).await.unwrap(), 0);
+
+    let (sender, failing) = Body::channel();
+    sender.abort();
+    assert!(converter.body_to_stdout(failing, false).await.is_err());
+}
+
+#[tokio::test]
+async fn test_body_to_multipart() {
+    let converter = StreamConverter::new();
+    let payload = "preamble\r\n\
+        --b0undary\r\n\
+        Content-Disposition: form-data; name=\"title\"\r\n\
+        \r\n\
+        Report\r\n\
+        --b0undary\r\n\
+        Content-Disposition: form-data; name=\"file\"; filename=\"q\\\"1\\\".csv\"\r\n\
+        Content-Type: text/csv\r\n\
+        \r\n\
+        a,b\r\n1,2\r\n\
+        --b0undary\r\n\
+        content-disposition: attachment; filename=\"fallback.txt\"; filename*=UTF-8''%E2%82%AC%20rates.txt\r\n\
+        \r\n\
+        \r\n\
+        --b0undary--\r\n\
+        epilogue";
+    let chunks: Vec<Bytes> = payload.as_bytes().chunks(7).map(Bytes::copy_from_slice).collect();
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+
+    let parts = converter.body_to_multipart(body, "b0undary").await.unwrap();
+    assert_eq!(parts.len(), 3);
+
+    assert_eq!(parts[0].name.as_deref(), Some("title"));
+    assert_eq!(parts[0].filename, None);
+    assert_eq!(parts[0].content_type, None);
+    assert_eq!(parts[0].body, "Report");
+
+    assert_eq!(parts[1].name.as_deref(), Some("file"));
+    assert_eq!(parts[1].filename.as_deref(), Some("q\"1\".csv"));
+    assert_eq!(parts[1].content_type.as_deref(), Some("text/csv"));
+    assert_eq!(parts[1].body, "a,b\r\n1,2");
+
+    assert_eq!(parts[2].name, None);
+    assert_eq!(parts[2].filename.as_deref(), Some("€ rates.txt"));
+    assert_eq!(parts[2].body, "");
+
+    let err = converter
+        .body_to_multipart(Body::from("--b0undary\r\n\r\nno end"), "b0undary")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::FramingError(_)));
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_with_framing() {
+    use b_stb::converter::Framing;
+    use hyper::Response;
+
+    let converter = StreamConverter::new();
+
+    let resp = Response::builder()
+        .header("Transfer-Encoding", "gzip, Chunked")
+        .header("Content-Length", "4")
+        .body(Body::from("data"))
+        .unwrap();
+    let (bytes, framing) = converter.body_to_bytes_with_framing(resp).await.unwrap();
+    assert_eq!(bytes, b"data");
+    assert_eq!(framing, Framing::Chunked);
+
+    let resp = Response::builder().header("Content-Length", " 4 ").body(Body::from("data")).unwrap();
+    let (_, framing) = converter.body_to_bytes_with_framing(resp).await.unwrap();
+    assert_eq!(framing, Framing::ContentLength(4));
+
+    let resp = Response::builder().header("Content-Length", "four").body(Body::from("data")).unwrap();
+    let (_, framing) = converter.body_to_bytes_with_framing(resp).await.unwrap();
+    assert_eq!(framing, Framing::Unknown);
+
+    let (_, framing) = converter.body_to_bytes_with_framing(Response::new(Body::empty())).await.unwrap();
+    assert_eq!(framing, Framing::Unknown);
+}
+
+#[tokio::test]
+async fn test_body_to_lines_limited() {
+    let converter = StreamConverter::new();
+
+    // The body never ends, so reading it in full would hang
+    let (mut sender, body) = Body::channel();
+    let keep_open = tokio::spawn(async move {
+        sender.send_data(Bytes::from("one\r\ntwo\nthr")).await.unwrap();
+        sender.send_data(Bytes::from("ee\nfour\n")).await.unwrap();
+        std::future::pending::<()>().await;
+    });
+
+    let lines = converter.body_to_lines_limited(body, 3).await.unwrap();
+    assert_eq!(lines, vec!["one", "two", "three"]);
+    keep_open.abort();
+
+    let lines = converter.body_to_lines_limited(Body::from("only\nlines"), 10).await.unwrap();
+    assert_eq!(lines, vec!["only", "lines"]);
+
+    let lines = converter.body_to_lines_limited(Body::from("ignored"), 0).await.unwrap();
+    assert!(lines.is_empty());
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_with_newline_index() {
+    let converter = StreamConverter::new();
+    let chunks = vec![Bytes::from("first\nsec"), Bytes::from("ond\n"), Bytes::from("\nlast")];
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+
+    let (bytes, index) = converter.body_to_bytes_with_newline_index(body).await.unwrap();
+    assert_eq!(bytes, b"first\nsecond\n\nlast");
+    assert_eq!(index, vec![5, 12, 13]);
+    assert_eq!(&bytes[index[0] as usize + 1..index[1] as usize], b"second");
+    assert_eq!(&bytes[index[2] as usize + 1..], b"last");
+
+    let (_, index) = converter.body_to_bytes_with_newline_index(Body::from("no newline")).await.unwrap();
+    assert!(index.is_empty());
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_filtered() {
+    let converter = StreamConverter::new();
+    let forbidden: &[&[u8]] = &[b"<script", b"DROP TABLE", b""];
+
+    // A token split across three chunks is still caught
+    let chunks = vec![Bytes::from("safe <scr"), Bytes::from("i"), Bytes::from("pt>alert(1)")];
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+    let err = converter.body_to_bytes_filtered(body, forbidden).await.unwrap_err();
+    match &err {
+        b_stb::StreamConverterError::PolicyViolation { token } => assert_eq!(token, b"<script"),
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert_eq!(err.to_string(), "Forbidden token \"<script\" found in body");
+
+    let chunks = vec![Bytes::from("<scri"), Bytes::from("bble and DROP"), Bytes::from(" TABLES")];
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+    let err = converter.body_to_bytes_filtered(body, forbidden).await.unwrap_err();
+    assert!(matches!(err, b_stb::StreamConverterError::PolicyViolation { token } if token == b"DROP TABLE"));
+
+    let bytes = converter.body_to_bytes_filtered(Body::from("all clear"), forbidden).await.unwrap();
+    assert_eq!(bytes, b"all clear");
+}
+
+#[tokio::test]
+async fn test_body_to_normalized_string() {
+    let converter = StreamConverter::new();
+    let expected = converter
+        .body_to_normalized_string(Body::from("<h1>Welcome Back</h1>\n<p>Ünïcode OK</p>"))
+        .await
+        .unwrap();
+    let actual = converter
+        .body_to_normalized_string(Body::from("\r\n  <h1>WELCOME   back</h1>\r\n\t<p>ÜNÏCODE ok</p>\r\n"))
+        .await
+        .unwrap();
+    assert_eq!(expected, "<h1>welcome back</h1> <p>ünïcode ok</p>");
+    assert_eq!(actual, expected);
+
+    assert_eq!(converter.body_to_normalized_string(Body::from(" \n\t ")).await.unwrap(), "");
+}
+
+#[tokio::test]
+async fn test_body_to_env_map() {
+    use b_stb::error::StreamConverterError;
+
+    let converter = StreamConverter::new();
+    let env = "# database settings\r\n\
+               DB_HOST=localhost\r\n\
+               \r\n\
+               export DB_PORT = 5432 # default port\n\
+               GREETING=\"hello # not a comment\\n\\\"friend\\\"\"\n\
+               RAW='C:\\path\\n'\n\
+               EMPTY=\n\
+               DB_HOST=db.internal\n";
+    let vars = converter.body_to_env_map(Body::from(env)).await.unwrap();
+    assert_eq!(vars.len(), 5);
+    assert_eq!(vars["DB_HOST"], "db.internal");
+    assert_eq!(vars["DB_PORT"], "5432");
+    assert_eq!(vars["GREETING"], "hello # not a comment\n\"friend\"");
+    assert_eq!(vars["RAW"], "C:\\path\\n");
+    assert_eq!(vars["EMPTY"], "");
+
+    for (input, line, content) in [
+        ("A=1\nnot a variable\n", 2, "not a variable"),
+        ("A=1\n\n# note\nB=\"unterminated\n", 4, "B=\"unterminated"),
+        ("=value\n", 1, "=value"),
+        ("A='x' trailing\n", 1, "A='x' trailing"),
+    ] {
+        match converter.body_to_env_map(Body::from(input)).await {
+            Err(StreamConverterError::EnvSyntaxError { line: l, content: c }) => {
+                assert_eq!((l, c.as_str()), (line, content));
+            }
+            other => panic!("expected EnvSyntaxError for {:?}, got {:?}", input, other),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_with_classes() {
+    use b_stb::converter::ByteClasses;
+
+    let converter = StreamConverter::new();
+    let chunks = vec![Bytes::from(&b"ab c\r\n"[..]), Bytes::from(&b"\x00\x1b\x7f\xc3\xa9"[..])];
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+
+    let (bytes, classes) = converter.body_to_bytes_with_classes(body).await.unwrap();
+    assert_eq!(bytes, b"ab c\r\n\x00\x1b\x7f\xc3\xa9");
+    assert_eq!(
+        classes,
+        ByteClasses { printable: 3, whitespace: 3, control: 3, high_bit: 2 }
+    );
+    assert_eq!(classes.total(), bytes.len());
+}
+
+#[tokio::test]
+async fn test_append_body_to_file() {
+    let converter = StreamConverter::new();
+    let path = std::env::temp_dir().join(format!("b_stb_append_{}.bin", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    // The first attempt fails partway, leaving what was received
+    let (mut sender, body) = Body::channel();
+    tokio::spawn(async move {
+        sender.send_data(Bytes::from("first half, ")).await.unwrap();
+        sender.abort();
+    });
+    assert!(converter.append_body_to_file(body, &path).await.is_err());
+    let have = std::fs::metadata(&path).unwrap().len();
+    assert_eq!(have, 12);
+
+    // The resumed body is appended after it
+    let appended = converter.append_body_to_file(Body::from("second half"), &path).await.unwrap();
+    assert_eq!(appended, 11);
+    assert_eq!(std::fs::read(&path).unwrap(), b"first half, second half");
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_replace() {
+    let converter = StreamConverter::new();
+    let chunks = vec![
+        Bytes::from("Hello {{na"),
+        Bytes::from("me}}, "),
+        Bytes::from("bye {{"),
+        Bytes::from("name}}{{name"),
+    ];
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+
+    let bytes = converter.body_to_bytes_replace(body, b"{{name}}", b"Ann").await.unwrap();
+    assert_eq!(bytes, b"Hello Ann, bye Ann{{name");
+
+    // Longer replacements containing the pattern are not rescanned
+    let bytes = converter.body_to_bytes_replace(Body::from("aaa"), b"a", b"aa").await.unwrap();
+    assert_eq!(bytes, b"aaaaaa");
+
+    // Non-overlapping, left to right
+    let bytes = converter.body_to_bytes_replace(Body::from("aaaa"), b"aa", b"b").await.unwrap();
+    assert_eq!(bytes, b"bb");
+
+    let bytes = converter.body_to_bytes_replace(Body::from("same"), b"", b"x").await.unwrap();
+    assert_eq!(bytes, b"same");
+}
+
+#[tokio::test]
+async fn test_body_to_bytes_detect_compression() {
+    use b_stb::converter::Compression;
+
+    let converter = StreamConverter::new();
+
+    // The magic bytes may arrive split across chunks
+    let chunks = vec![Bytes::from(&b"\x28\xb5"[..]), Bytes::from(&b"\x2f\xfd\x00payload"[..])];
+    let body = Body::wrap_stream(futures_util::stream::iter(chunks.into_iter().map(Ok::<_, hyper::Error>)));
+    let (bytes, format) = converter.body_to_bytes_detect_compression(body).await.unwrap();
+    assert_eq!(bytes, b"\x28\xb5\x2f\xfd\x00payload");
+    assert_eq!(format, Some(Compression::Zstd));
+
+    let cases: [(&[u8], Option<Compression>); 6] = [
+        (b"\x1f\x8b\x08", Some(Compression::Gzip)),
+        (b"BZh91AY", Some(Compression::Bzip2)),
+        (b"\xfd7zXZ\x00\x00", Some(Compression::Xz)),
+        (b"\x04\x22\x4d\x18", Some(Compression::Lz4)),
+        (b"plain text", None),
+        (b"", None),
+    ];
+    for (input, expected) in cases {
+        let (bytes, format) = converter
+            .body_to_bytes_detect_compression(Body::from(input.to_vec()))
+            .await
+            .unwrap();
+        assert_eq!(bytes, input);
+        assert_eq!(format, expected);
+    }
+}
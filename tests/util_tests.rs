@@ -1,4 +1,4 @@
-use b_stb::util::{bytes_to_string, concat_bytes, default_buffer_size};
+use b_stb::util::{bytes_to_string, concat_bytes, concat_to_bytes, default_buffer_size, guess_extension};
 use bytes::Bytes;
 
 #[test]
@@ -20,6 +20,21 @@ fn test_concat_bytes() {
     assert_eq!(result, b"Hello, World!");
 }
 
+#[test]
+fn test_concat_to_bytes() {
+    let single = Bytes::from(vec![1u8, 2, 3]);
+    let result = concat_to_bytes(vec![single.clone()]);
+    assert_eq!(result, single);
+    assert_eq!(result.as_ptr(), single.as_ptr());
+
+    let chunks: Vec<Bytes> = (0..1000u32).map(|i| Bytes::from(i.to_string())).collect();
+    let expected = concat_bytes(chunks.clone());
+    let result = concat_to_bytes(chunks);
+    assert_eq!(&result[..], &expected[..]);
+
+    assert!(concat_to_bytes(Vec::new()).is_empty());
+}
+
 #[test]
 fn test_default_buffer_size() {
     assert_eq!(default_buffer_size(), 8192);
@@ -30,4 +45,95 @@ fn test_invalid_utf8() {
     let invalid_utf8 = vec![0xFF, 0xFF, 0xFF];
     let result = bytes_to_string(&invalid_utf8);
     assert!(result.is_none());
-} 
\ No newline at end of file
+} 
+#[test]
+fn test_parse_header_block() {
+    use b_stb::util::parse_header_block;
+
+    let block = b"Host: example.com\r\nSet-Cookie: a=1\r\nSet-Cookie:  b=2 \r\n\r\n";
+    let headers = parse_header_block(block).unwrap();
+    assert_eq!(headers, vec![
+        ("Host".to_string(), "example.com".to_string()),
+        ("Set-Cookie".to_string(), "a=1".to_string()),
+        ("Set-Cookie".to_string(), "b=2".to_string()),
+    ]);
+}
+
+#[test]
+fn test_parse_header_block_invalid_line() {
+    use b_stb::util::parse_header_block;
+
+    let err = parse_header_block(b"Host: example.com\r\nnot a header\r\n").unwrap_err();
+    assert!(err.to_string().contains("not a header"));
+}
+
+#[test]
+fn test_lines_borrowed() {
+    use b_stb::util::lines_borrowed;
+
+    let lines: Vec<&[u8]> = lines_borrowed(b"a\r\nb\n\nc").collect();
+    assert_eq!(lines, vec![&b"a"[..], &b"b"[..], &b""[..], &b"c"[..]]);
+
+    let lines: Vec<&[u8]> = lines_borrowed(b"a\n").collect();
+    assert_eq!(lines, vec![&b"a"[..]]);
+
+    assert_eq!(lines_borrowed(b"").count(), 0);
+}
+
+#[test]
+fn test_guess_extension() {
+    assert_eq!(guess_extension(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"), Some("png"));
+    assert_eq!(guess_extension(b"\x1f\x8b\x08\x00"), Some("gz"));
+    assert_eq!(guess_extension(b"RIFF\x24\0\0\0WEBPVP8 "), Some("webp"));
+    assert_eq!(guess_extension(b"\0\0\0\x18ftypmp42"), Some("mp4"));
+    assert_eq!(guess_extension(b"hello world"), None);
+    assert_eq!(guess_extension(b""), None);
+}
+
+#[test]
+fn test_guess_extension_offset_signatures() {
+    assert_eq!(guess_extension(b"RIFF\x24\0\0\0WAVEfmt "), Some("wav"));
+    assert_eq!(guess_extension(b"\0\0\0\x20ftypisom\0\0\x02\0"), Some("mp4"));
+
+    // The form type or box type alone, without a valid container header
+    assert_eq!(guess_extension(b"01234567WAVE"), None);
+    assert_eq!(guess_extension(b"01234567WEBP"), None);
+    assert_eq!(guess_extension(b"RIFF\x24\0\0\0AVI LIST"), None);
+    assert_eq!(guess_extension(b"see ftyp box"), None);
+    assert_eq!(guess_extension(b"\0\0\0\x18ftyp\0\x01\x02\x03"), None);
+    assert_eq!(guess_extension(b"\0\0\0\x19ftypmp42"), None);
+}
+
+#[test]
+fn test_guess_extension_bmp_and_id3_headers() {
+    // A 2x2 24-bit BMP: 14-byte file header, 40-byte DIB header, 16 bytes of pixels
+    let mut bmp = b"BM".to_vec();
+    bmp.extend_from_slice(&70u32.to_le_bytes());
+    bmp.extend_from_slice(&[0; 4]);
+    bmp.extend_from_slice(&54u32.to_le_bytes());
+    bmp.extend_from_slice(&40u32.to_le_bytes());
+    assert_eq!(guess_extension(&bmp), Some("bmp"));
+
+    // ID3v2.4 tag header with a syncsafe size
+    assert_eq!(guess_extension(b"ID3\x04\x00\x00\x00\x00\x02\x01"), Some("mp3"));
+    assert_eq!(guess_extension(b"ID3\x03\x00\x80\x00\x00\x00\x7f"), Some("mp3"));
+
+    // Text that merely starts with the magic bytes
+    assert_eq!(guess_extension(b"BMW 3 Series owner's manual, 2019 edition"), None);
+    assert_eq!(guess_extension(b"BM"), None);
+    assert_eq!(guess_extension(b"ID3 tag list: title, artist, album"), None);
+    assert_eq!(guess_extension(b"ID3"), None);
+
+    // Headers with invalid fields
+    let mut reserved_set = bmp.clone();
+    reserved_set[6] = 1;
+    assert_eq!(guess_extension(&reserved_set), None);
+    let mut bad_dib = bmp.clone();
+    bad_dib[14] = 41;
+    assert_eq!(guess_extension(&bad_dib), None);
+    bad_dib[14..18].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert_eq!(guess_extension(&bad_dib), None);
+    assert_eq!(guess_extension(b"ID3\x05\x00\x00\x00\x00\x00\x00"), None);
+    assert_eq!(guess_extension(b"ID3\x03\x00\x01\x00\x00\x00\x00"), None);
+    assert_eq!(guess_extension(b"ID3\x03\x00\x00\x00\x80\x00\x00"), None);
+}